@@ -0,0 +1,529 @@
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::expressions::expression::Expression;
+use crate::expressions::helpers::{and, atomic, not, or};
+use crate::expressions::operator::BinaryOperator;
+
+/// An index of a node in a [`Bdd`].
+pub type NodeId = usize;
+
+/// The most non-terminal nodes a [`Bdd`] can have, since some functions need exponentially many nodes in every order.
+pub const MAX_BDD_NODES: usize = 100_000;
+
+/// The most nodes that can be expanded when converting a function back into an expression,
+/// since shared nodes are expanded every time they are reached.
+pub const MAX_EXPANDED_NODES: usize = 10_000;
+
+/// How the atomic values of an expression are ordered in a [`Bdd`].
+/// The size of the diagram depends heavily on the order, and finding the best order is NP-hard.
+#[derive(Debug, Default, Copy, Clone, PartialEq, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum VariableOrder {
+    /// Ordered by name.
+    #[default]
+    Alphabetical,
+    /// Ordered by the first occurrence from left to right,
+    /// which keeps atomic values that are used together close to each other.
+    FirstOccurrence,
+    /// The atomic values that occur most often first, ordered by name if they occur equally often.
+    MostFrequent,
+}
+
+impl VariableOrder {
+    pub fn variables(self, expression: &Expression) -> Vec<String> {
+        let mut occurrences = vec![];
+        collect_atomics(expression, &mut occurrences);
+        match self {
+            VariableOrder::Alphabetical => {
+                occurrences.sort();
+                occurrences.dedup();
+            }
+            VariableOrder::FirstOccurrence => {
+                let mut seen = std::collections::HashSet::new();
+                occurrences.retain(|name| seen.insert(*name));
+            }
+            VariableOrder::MostFrequent => {
+                let mut counts = HashMap::<&str, usize>::new();
+                for name in &occurrences {
+                    *counts.entry(name).or_default() += 1;
+                }
+                occurrences.sort_by(|left, right| counts[right].cmp(&counts[left]).then(left.cmp(right)));
+                occurrences.dedup();
+            }
+        }
+        occurrences.into_iter().map(String::from).collect()
+    }
+}
+
+fn collect_atomics<'a>(expression: &'a Expression, atomics: &mut Vec<&'a str>) {
+    match expression {
+        Expression::Not(expression) => collect_atomics(expression, atomics),
+        Expression::Binary { left, right, .. } => {
+            collect_atomics(left, atomics);
+            collect_atomics(right, atomics);
+        }
+        Expression::Atomic(name) => atomics.push(name),
+        Expression::Constant(_) => {}
+    }
+}
+
+/// A node that tests a variable, and continues to `low` if it is false, or `high` if it is true.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+struct Node {
+    /// The index of the variable in the order, or `usize::MAX` for the terminal nodes.
+    level: usize,
+    low: NodeId,
+    high: NodeId,
+}
+
+/// A reduced ordered binary decision diagram, where every function over the variables has exactly one node,
+/// so two functions are equivalent exactly when they are the same node.
+/// The nodes are shared between all functions built in the same diagram.
+#[derive(Debug)]
+pub struct Bdd {
+    variables: Vec<String>,
+    nodes: Vec<Node>,
+    /// Finds the existing node for a level and children, so no two nodes are equal.
+    unique: HashMap<Node, NodeId>,
+    ite_cache: HashMap<(NodeId, NodeId, NodeId), NodeId>,
+}
+
+/// A node of a [`Bdd`] for serialization.
+#[derive(Debug, PartialEq, Serialize)]
+pub struct BddNode {
+    pub id: NodeId,
+    pub variable: String,
+    /// The node to continue to if the variable is false.
+    pub low: NodeId,
+    /// The node to continue to if the variable is true.
+    pub high: NodeId,
+}
+
+impl Bdd {
+    /// The terminal node for functions that are always false.
+    pub const FALSE: NodeId = 0;
+    /// The terminal node for functions that are always true.
+    pub const TRUE: NodeId = 1;
+
+    /// Creates an empty diagram where the variables are tested in the given order.
+    pub fn new(variables: Vec<String>) -> Self {
+        let terminal = |id| Node { level: usize::MAX, low: id, high: id };
+        Self {
+            variables,
+            nodes: vec![terminal(Self::FALSE), terminal(Self::TRUE)],
+            unique: HashMap::new(),
+            ite_cache: HashMap::new(),
+        }
+    }
+
+    /// Creates a diagram for the expression, with the atomic values in the given order.
+    /// Returns `None` if the diagram would have more than [`MAX_BDD_NODES`] nodes.
+    pub fn from_expression(expression: &Expression, order: VariableOrder) -> Option<(Self, NodeId)> {
+        let mut bdd = Self::new(order.variables(expression));
+        let root = bdd.build(expression)?;
+        Some((bdd, root))
+    }
+
+    pub fn variables(&self) -> &[String] {
+        &self.variables
+    }
+
+    /// Builds the function of the expression.
+    /// Atomic values that are not variables of the diagram are added after the existing variables.
+    /// This and every other operation that creates nodes return `None` if the diagram would have more than [`MAX_BDD_NODES`] nodes.
+    pub fn build(&mut self, expression: &Expression) -> Option<NodeId> {
+        match expression {
+            Expression::Not(expression) => {
+                let function = self.build(expression)?;
+                self.not(function)
+            }
+            Expression::Binary { left, operator, right } => {
+                let left = self.build(left)?;
+                let right = self.build(right)?;
+                self.apply(*operator, left, right)
+            }
+            Expression::Atomic(name) => self.variable(name),
+            Expression::Constant(value) => Some(if *value { Self::TRUE } else { Self::FALSE }),
+        }
+    }
+
+    /// The function that is true exactly when the variable is true.
+    pub fn variable(&mut self, name: &str) -> Option<NodeId> {
+        let level = match self.variables.iter().position(|variable| variable == name) {
+            Some(level) => level,
+            None => {
+                self.variables.push(name.to_string());
+                self.variables.len() - 1
+            }
+        };
+        self.make(level, Self::FALSE, Self::TRUE)
+    }
+
+    /// If-then-else, the function that is `then` where `condition` is true, and `otherwise` where it is false.
+    /// Every other operation is built on this.
+    pub fn ite(&mut self, condition: NodeId, then: NodeId, otherwise: NodeId) -> Option<NodeId> {
+        match (condition, then, otherwise) {
+            (Self::TRUE, _, _) => return Some(then),
+            (Self::FALSE, _, _) => return Some(otherwise),
+            (_, Self::TRUE, Self::FALSE) => return Some(condition),
+            _ if then == otherwise => return Some(then),
+            _ => {}
+        }
+        if let Some(result) = self.ite_cache.get(&(condition, then, otherwise)) {
+            return Some(*result);
+        }
+        let level = [condition, then, otherwise].into_iter()
+            .map(|node| self.nodes[node].level)
+            .min()
+            .unwrap_or(usize::MAX);
+        let (condition_low, condition_high) = self.cofactors(condition, level);
+        let (then_low, then_high) = self.cofactors(then, level);
+        let (otherwise_low, otherwise_high) = self.cofactors(otherwise, level);
+        let low = self.ite(condition_low, then_low, otherwise_low)?;
+        let high = self.ite(condition_high, then_high, otherwise_high)?;
+        let result = self.make(level, low, high)?;
+        self.ite_cache.insert((condition, then, otherwise), result);
+        Some(result)
+    }
+
+    pub fn not(&mut self, function: NodeId) -> Option<NodeId> {
+        self.ite(function, Self::FALSE, Self::TRUE)
+    }
+
+    /// Combines two functions with a binary operator.
+    pub fn apply(&mut self, operator: BinaryOperator, left: NodeId, right: NodeId) -> Option<NodeId> {
+        match operator {
+            BinaryOperator::And => self.ite(left, right, Self::FALSE),
+            BinaryOperator::Or => self.ite(left, Self::TRUE, right),
+            BinaryOperator::Implication => self.ite(left, right, Self::TRUE),
+            BinaryOperator::Biconditional => {
+                let not_right = self.not(right)?;
+                self.ite(left, right, not_right)
+            }
+            BinaryOperator::Xor => {
+                let not_right = self.not(right)?;
+                self.ite(left, not_right, right)
+            }
+            BinaryOperator::Nand => {
+                let and = self.apply(BinaryOperator::And, left, right)?;
+                self.not(and)
+            }
+            BinaryOperator::Nor => {
+                let or = self.apply(BinaryOperator::Or, left, right)?;
+                self.not(or)
+            }
+        }
+    }
+
+    /// The function with the variable fixed to the value.
+    /// Returns the function unchanged if the variable is not in the diagram.
+    pub fn restrict(&mut self, function: NodeId, variable: &str, value: bool) -> Option<NodeId> {
+        match self.variables.iter().position(|name| name == variable) {
+            Some(level) => self.restrict_level(function, level, value, &mut HashMap::new()),
+            None => Some(function),
+        }
+    }
+
+    fn restrict_level(&mut self, function: NodeId, level: usize, value: bool, cache: &mut HashMap<NodeId, NodeId>) -> Option<NodeId> {
+        let node = self.nodes[function];
+        if node.level > level {
+            return Some(function);
+        }
+        if node.level == level {
+            return Some(if value { node.high } else { node.low });
+        }
+        if let Some(result) = cache.get(&function) {
+            return Some(*result);
+        }
+        let low = self.restrict_level(node.low, level, value, cache)?;
+        let high = self.restrict_level(node.high, level, value, cache)?;
+        let result = self.make(node.level, low, high)?;
+        cache.insert(function, result);
+        Some(result)
+    }
+
+    /// The function that is true if the function is true for some value of the variable.
+    pub fn exists(&mut self, function: NodeId, variable: &str) -> Option<NodeId> {
+        let low = self.restrict(function, variable, false)?;
+        let high = self.restrict(function, variable, true)?;
+        self.apply(BinaryOperator::Or, low, high)
+    }
+
+    /// The function that is true if the function is true for both values of the variable.
+    pub fn for_all(&mut self, function: NodeId, variable: &str) -> Option<NodeId> {
+        let low = self.restrict(function, variable, false)?;
+        let high = self.restrict(function, variable, true)?;
+        self.apply(BinaryOperator::And, low, high)
+    }
+
+    /// Counts the assignments of all variables of the diagram that make the function true.
+    /// The count only fits if there are at most [`MAX_COUNTED_ATOMICS`](crate::expressions::sat::MAX_COUNTED_ATOMICS) variables.
+    pub fn count_models(&self, function: NodeId) -> u128 {
+        let level = self.level(function);
+        self.count_below(function, &mut HashMap::new()) << level
+    }
+
+    /// Counts the assignments of the variables from the level of the node and down.
+    fn count_below(&self, function: NodeId, cache: &mut HashMap<NodeId, u128>) -> u128 {
+        match function {
+            Self::FALSE => return 0,
+            Self::TRUE => return 1,
+            _ => {}
+        }
+        if let Some(count) = cache.get(&function) {
+            return *count;
+        }
+        let node = self.nodes[function];
+        let count = [node.low, node.high].into_iter()
+            .map(|child| self.count_below(child, cache) << (self.level(child) - node.level - 1))
+            .sum();
+        cache.insert(function, count);
+        count
+    }
+
+    /// Converts the function back into an expression, by expanding every node into `x ⋀ high ⋁ ¬x ⋀ low`.
+    /// Shared nodes are expanded every time they are reached,
+    /// so `None` is returned if more than [`MAX_EXPANDED_NODES`] nodes would be expanded.
+    pub fn to_expression(&self, function: NodeId) -> Option<Expression> {
+        (self.expanded_nodes(function, &mut HashMap::new()) <= MAX_EXPANDED_NODES).then(|| self.expand(function))
+    }
+
+    /// The number of non-terminal nodes visited when expanding the function, counting shared nodes every time.
+    fn expanded_nodes(&self, function: NodeId, cache: &mut HashMap<NodeId, usize>) -> usize {
+        if function == Self::FALSE || function == Self::TRUE {
+            return 0;
+        }
+        if let Some(count) = cache.get(&function) {
+            return *count;
+        }
+        let node = self.nodes[function];
+        let count = self.expanded_nodes(node.low, cache)
+            .saturating_add(self.expanded_nodes(node.high, cache))
+            .saturating_add(1);
+        cache.insert(function, count);
+        count
+    }
+
+    fn expand(&self, function: NodeId) -> Expression {
+        match function {
+            Self::FALSE => return Expression::Constant(false),
+            Self::TRUE => return Expression::Constant(true),
+            _ => {}
+        }
+        let node = self.nodes[function];
+        let variable = atomic(self.variables[node.level].as_str());
+        match (node.low, node.high) {
+            (Self::FALSE, Self::TRUE) => variable,
+            (Self::TRUE, Self::FALSE) => not(variable),
+            (Self::FALSE, high) => and(variable, self.expand(high)),
+            (low, Self::FALSE) => and(not(variable), self.expand(low)),
+            (Self::TRUE, high) => or(not(variable), self.expand(high)),
+            (low, Self::TRUE) => or(variable, self.expand(low)),
+            (low, high) => or(
+                and(variable.clone(), self.expand(high)),
+                and(not(variable), self.expand(low)),
+            ),
+        }
+    }
+
+    /// The non-terminal nodes reachable from the function, with the root first.
+    pub fn reachable_nodes(&self, function: NodeId) -> Vec<BddNode> {
+        let mut visited = vec![false; self.nodes.len()];
+        let mut stack = vec![function];
+        let mut nodes = vec![];
+        while let Some(id) = stack.pop() {
+            if id == Self::FALSE || id == Self::TRUE || visited[id] {
+                continue;
+            }
+            visited[id] = true;
+            let node = self.nodes[id];
+            nodes.push(BddNode { id, variable: self.variables[node.level].clone(), low: node.low, high: node.high });
+            stack.push(node.high);
+            stack.push(node.low);
+        }
+        nodes
+    }
+
+    /// The node for a level and children, which is reduced away if both children are equal.
+    /// Returns `None` if a new node is needed, but the diagram already has [`MAX_BDD_NODES`] non-terminal nodes.
+    fn make(&mut self, level: usize, low: NodeId, high: NodeId) -> Option<NodeId> {
+        if low == high {
+            return Some(low);
+        }
+        let node = Node { level, low, high };
+        if let Some(id) = self.unique.get(&node) {
+            return Some(*id);
+        }
+        if self.unique.len() >= MAX_BDD_NODES {
+            return None;
+        }
+        self.nodes.push(node);
+        self.unique.insert(node, self.nodes.len() - 1);
+        Some(self.nodes.len() - 1)
+    }
+
+    /// The children of the node if it tests the variable at the level, otherwise the node itself for both.
+    fn cofactors(&self, function: NodeId, level: usize) -> (NodeId, NodeId) {
+        let node = self.nodes[function];
+        if node.level == level {
+            (node.low, node.high)
+        } else {
+            (function, function)
+        }
+    }
+
+    /// The level of the node, where the terminal nodes are below every variable.
+    fn level(&self, function: NodeId) -> usize {
+        self.nodes[function].level.min(self.variables.len())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::expressions::bdd::{Bdd, VariableOrder};
+    use crate::expressions::helpers::{and, atomic, chain, constant, iff, implies, nand, nor, not, or, xor};
+    use crate::expressions::operator::BinaryOperator;
+
+    fn variables(names: &[&str]) -> Vec<String> {
+        names.iter().map(|name| name.to_string()).collect()
+    }
+
+    #[test]
+    fn test_equivalent_expressions_are_the_same_node() {
+        let mut bdd = Bdd::new(variables(&["a", "b"]));
+        let left = bdd.build(&not(and(atomic("a"), atomic("b")))).unwrap();
+        let right = bdd.build(&or(not(atomic("b")), not(atomic("a")))).unwrap();
+        assert_eq!(left, right);
+        let implication = bdd.build(&implies(atomic("a"), atomic("b"))).unwrap();
+        assert_ne!(left, implication);
+    }
+
+    #[test]
+    fn test_tautology_and_contradiction() {
+        let mut bdd = Bdd::new(vec![]);
+        assert_eq!(bdd.build(&or(atomic("a"), not(atomic("a")))).unwrap(), Bdd::TRUE);
+        assert_eq!(bdd.build(&and(atomic("a"), not(atomic("a")))).unwrap(), Bdd::FALSE);
+        assert_eq!(bdd.build(&constant(true)).unwrap(), Bdd::TRUE);
+    }
+
+    #[test]
+    fn test_every_operator() {
+        let (a, b) = (atomic("a"), atomic("b"));
+        let mut bdd = Bdd::new(variables(&["a", "b"]));
+        for (expression, count) in [
+            (and(a.clone(), b.clone()), 1),
+            (or(a.clone(), b.clone()), 3),
+            (implies(a.clone(), b.clone()), 3),
+            (iff(a.clone(), b.clone()), 2),
+            (xor(a.clone(), b.clone()), 2),
+            (nand(a.clone(), b.clone()), 3),
+            (nor(a.clone(), b.clone()), 1),
+        ] {
+            let function = bdd.build(&expression).unwrap();
+            assert_eq!(bdd.count_models(function), count, "{expression}");
+        }
+    }
+
+    #[test]
+    fn test_restrict() {
+        let mut bdd = Bdd::new(variables(&["a", "b"]));
+        let function = bdd.build(&xor(atomic("a"), atomic("b"))).unwrap();
+        let restricted = bdd.restrict(function, "a", true).unwrap();
+        assert_eq!(restricted, bdd.build(&not(atomic("b"))).unwrap());
+        assert_eq!(bdd.restrict(function, "c", true).unwrap(), function);
+    }
+
+    #[test]
+    fn test_quantify() {
+        let mut bdd = Bdd::new(variables(&["a", "b"]));
+        let function = bdd.build(&and(atomic("a"), atomic("b"))).unwrap();
+        let exists = bdd.exists(function, "a").unwrap();
+        assert_eq!(exists, bdd.build(&atomic("b")).unwrap());
+        assert_eq!(bdd.for_all(function, "a").unwrap(), Bdd::FALSE);
+        let function = bdd.build(&or(atomic("a"), atomic("b"))).unwrap();
+        let for_all = bdd.for_all(function, "a").unwrap();
+        assert_eq!(for_all, bdd.build(&atomic("b")).unwrap());
+    }
+
+    #[test]
+    fn test_count_models_skipped_levels() {
+        let mut bdd = Bdd::new(variables(&["a", "b", "c"]));
+        let function = bdd.build(&and(atomic("a"), atomic("c"))).unwrap();
+        assert_eq!(bdd.count_models(function), 2);
+        let function = bdd.build(&atomic("b")).unwrap();
+        assert_eq!(bdd.count_models(function), 4);
+        assert_eq!(bdd.count_models(Bdd::TRUE), 8);
+        assert_eq!(bdd.count_models(Bdd::FALSE), 0);
+    }
+
+    #[test]
+    fn test_to_expression_is_equivalent() {
+        let (a, b, c) = (atomic("a"), atomic("b"), atomic("c"));
+        let mut bdd = Bdd::new(variables(&["a", "b", "c"]));
+        for expression in [
+            or(and(a.clone(), b.clone()), and(not(a.clone()), c.clone())),
+            iff(a.clone(), xor(b.clone(), c.clone())),
+            nor(a.clone(), implies(b.clone(), c.clone())),
+            a.clone(),
+            not(b.clone()),
+        ] {
+            let function = bdd.build(&expression).unwrap();
+            let converted = bdd.to_expression(function).unwrap();
+            assert_eq!(bdd.build(&converted).unwrap(), function, "{expression} became {converted}");
+        }
+        let function = bdd.build(&or(and(a.clone(), b.clone()), and(not(a), c))).unwrap();
+        assert_eq!(bdd.to_expression(function).unwrap().to_string(), "a ⋀ b ⋁ ¬a ⋀ c");
+    }
+
+    #[test]
+    fn test_to_expression_too_many_expanded_nodes() {
+        let variables = (0..20).map(|index| atomic(format!("x{index:02}")));
+        let expression = chain(BinaryOperator::Xor, variables).unwrap();
+        let (bdd, root) = Bdd::from_expression(&expression, VariableOrder::Alphabetical).unwrap();
+        assert_eq!(bdd.reachable_nodes(root).len(), 39);
+        assert_eq!(bdd.to_expression(root), None);
+    }
+
+    #[test]
+    fn test_too_many_nodes() {
+        let pairs = (0..20).map(|index| and(atomic(format!("x{index:02}")), atomic(format!("y{index:02}"))));
+        let expression = chain(BinaryOperator::Or, pairs).unwrap();
+        assert!(Bdd::from_expression(&expression, VariableOrder::Alphabetical).is_none());
+        assert!(Bdd::from_expression(&expression, VariableOrder::FirstOccurrence).is_some());
+    }
+
+    #[test]
+    fn test_variable_order() {
+        let expression = or(and(atomic("b"), atomic("a")), and(atomic("b"), atomic("c")));
+        assert_eq!(VariableOrder::Alphabetical.variables(&expression), vec!["a", "b", "c"]);
+        assert_eq!(VariableOrder::FirstOccurrence.variables(&expression), vec!["b", "a", "c"]);
+        assert_eq!(VariableOrder::MostFrequent.variables(&expression), vec!["b", "a", "c"]);
+    }
+
+    #[test]
+    fn test_variable_order_changes_size() {
+        // x1 ⋀ y1 ⋁ x2 ⋀ y2 ⋁ … is linear when each pair is adjacent, and exponential when all x come first
+        let pairs = (0..6).map(|index| and(atomic(format!("x{index}")), atomic(format!("y{index}"))));
+        let expression = chain(BinaryOperator::Or, pairs).unwrap();
+        let (alphabetical, alphabetical_root) = Bdd::from_expression(&expression, VariableOrder::Alphabetical).unwrap();
+        let (first_occurrence, first_occurrence_root) = Bdd::from_expression(&expression, VariableOrder::FirstOccurrence).unwrap();
+        assert_eq!(first_occurrence.reachable_nodes(first_occurrence_root).len(), 12);
+        let alphabetical_size = alphabetical.reachable_nodes(alphabetical_root).len();
+        assert!(alphabetical_size > 100, "{alphabetical_size}");
+        assert_eq!(alphabetical.count_models(alphabetical_root), first_occurrence.count_models(first_occurrence_root));
+    }
+
+    #[test]
+    fn test_reachable_nodes() {
+        let (bdd, root) = Bdd::from_expression(&and(atomic("a"), atomic("b")), VariableOrder::Alphabetical).unwrap();
+        let nodes = bdd.reachable_nodes(root);
+        assert_eq!(nodes.len(), 2);
+        assert_eq!(nodes[0].id, root);
+        assert_eq!(nodes[0].variable, "a");
+        assert_eq!(nodes[0].low, Bdd::FALSE);
+        assert_eq!(nodes[1].variable, "b");
+        assert_eq!((nodes[1].low, nodes[1].high), (Bdd::FALSE, Bdd::TRUE));
+    }
+}
@@ -0,0 +1,154 @@
+use serde::Serialize;
+
+use crate::expressions::expression::Expression;
+use crate::expressions::helpers::{and, chain, not, xor};
+use crate::expressions::operator::BinaryOperator;
+use crate::expressions::sat::Assignment;
+
+/// Whether an expression is true for every, some or no values of its atomic values.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum Validity {
+    /// Always true.
+    Tautology,
+    /// Always false.
+    Contradiction,
+    /// True for some values, and false for others.
+    Contingent,
+}
+
+#[derive(Debug, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Classification {
+    pub validity: Validity,
+    /// Values for the atomic values that make the expression true, if there are any.
+    pub witness: Option<Assignment>,
+    /// Values for the atomic values that make the expression false, if there are any.
+    pub counterexample: Option<Assignment>,
+}
+
+impl Expression {
+    /// Classifies the expression as a tautology, contradiction or contingent,
+    /// by searching for a satisfying assignment of both the expression and its negation.
+    pub fn classify(&self) -> Classification {
+        let witness = self.satisfying_assignment();
+        let counterexample = Expression::Not(self.clone().into()).satisfying_assignment();
+        let validity = match (&witness, &counterexample) {
+            (Some(_), None) => Validity::Tautology,
+            (None, _) => Validity::Contradiction,
+            (Some(_), Some(_)) => Validity::Contingent,
+        };
+        Classification { validity, witness, counterexample }
+    }
+
+    /// Finds values for the atomic values where every premise is true and the conclusion is false,
+    /// or `None` if the premises entail the conclusion.
+    /// Without any premises, the conclusion is entailed only if it is a tautology.
+    pub fn entailment_counter_model(premises: &[Self], conclusion: &Self) -> Option<Assignment> {
+        let premises = chain(BinaryOperator::And, premises.iter().cloned()).unwrap_or(Expression::Constant(true));
+        and(premises, not(conclusion.clone())).satisfying_assignment()
+    }
+
+    /// Finds values for the atomic values of both expressions where they have different values,
+    /// or `None` if the expressions are logically equivalent.
+    pub fn distinguishing_assignment(&self, other: &Self) -> Option<Assignment> {
+        xor(self.clone(), other.clone()).satisfying_assignment()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::expressions::classify::Validity;
+    use crate::expressions::expression::Expression;
+    use crate::expressions::helpers::{and, atomic, constant, iff, implies, not, or};
+    use crate::expressions::sat::Assignment;
+
+    #[test]
+    fn test_classify_tautology() {
+        let classification = or(atomic("a"), not(atomic("a"))).classify();
+        assert_eq!(classification.validity, Validity::Tautology);
+        assert!(classification.witness.is_some());
+        assert!(classification.counterexample.is_none());
+    }
+
+    #[test]
+    fn test_classify_contradiction() {
+        let classification = and(atomic("a"), not(atomic("a"))).classify();
+        assert_eq!(classification.validity, Validity::Contradiction);
+        assert!(classification.witness.is_none());
+        assert!(classification.counterexample.is_some());
+    }
+
+    #[test]
+    fn test_classify_contingent() {
+        let classification = implies(atomic("a"), atomic("b")).classify();
+        assert_eq!(classification.validity, Validity::Contingent);
+        let counterexample = Assignment::from([("a".to_string(), true), ("b".to_string(), false)]);
+        assert_eq!(classification.counterexample, Some(counterexample));
+    }
+
+    #[test]
+    fn test_classify_constants() {
+        assert_eq!(constant(true).classify().validity, Validity::Tautology);
+        assert_eq!(constant(false).classify().validity, Validity::Contradiction);
+    }
+
+    #[test]
+    fn test_classify_hypothetical_syllogism() {
+        let (a, b, c) = (atomic("a"), atomic("b"), atomic("c"));
+        let premises = and(implies(a.clone(), b.clone()), implies(b, c.clone()));
+        assert_eq!(implies(premises, implies(a, c)).classify().validity, Validity::Tautology);
+    }
+
+    #[test]
+    fn test_distinguishing_assignment_equivalent() {
+        let (a, b) = (atomic("a"), atomic("b"));
+        let de_morgan = not(and(a.clone(), b.clone()));
+        assert!(de_morgan.distinguishing_assignment(&or(not(a.clone()), not(b.clone()))).is_none());
+        assert!(implies(a.clone(), b.clone()).distinguishing_assignment(&or(not(a), b)).is_none());
+    }
+
+    #[test]
+    fn test_distinguishing_assignment_not_equivalent() {
+        let (a, b) = (atomic("a"), atomic("b"));
+        let assignment = implies(a.clone(), b.clone()).distinguishing_assignment(&implies(b.clone(), a.clone()));
+        let expected = [
+            Assignment::from([("a".to_string(), false), ("b".to_string(), true)]),
+            Assignment::from([("a".to_string(), true), ("b".to_string(), false)]),
+        ];
+        assert!(expected.contains(&assignment.unwrap()));
+    }
+
+    #[test]
+    fn test_distinguishing_assignment_different_atomics() {
+        let assignment = atomic("a").distinguishing_assignment(&iff(atomic("a"), atomic("b"))).unwrap();
+        assert_eq!(assignment.len(), 2);
+        assert!(!assignment["b"]);
+        assert!(constant(true).distinguishing_assignment(&or(atomic("a"), not(atomic("a")))).is_none());
+    }
+
+    #[test]
+    fn test_entailment_modus_ponens() {
+        let (p, q) = (atomic("p"), atomic("q"));
+        assert!(Expression::entailment_counter_model(&[implies(p.clone(), q.clone()), p], &q).is_none());
+    }
+
+    #[test]
+    fn test_entailment_affirming_the_consequent() {
+        let (p, q) = (atomic("p"), atomic("q"));
+        let counter_model = Expression::entailment_counter_model(&[implies(p.clone(), q.clone()), q], &p);
+        assert_eq!(counter_model, Some(Assignment::from([("p".to_string(), false), ("q".to_string(), true)])));
+    }
+
+    #[test]
+    fn test_entailment_without_premises() {
+        assert!(Expression::entailment_counter_model(&[], &or(atomic("a"), not(atomic("a")))).is_none());
+        assert!(Expression::entailment_counter_model(&[], &atomic("a")).is_some());
+    }
+
+    #[test]
+    fn test_entailment_inconsistent_premises() {
+        let premises = [atomic("a"), not(atomic("a"))];
+        assert!(Expression::entailment_counter_model(&premises, &atomic("b")).is_none());
+    }
+}
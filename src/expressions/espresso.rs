@@ -0,0 +1,371 @@
+use std::cmp::Reverse;
+use std::collections::HashMap;
+
+use crate::expressions::expression::Expression;
+use crate::expressions::minimize::{Cube, DontCares, sum_of_products};
+use crate::expressions::operator::BinaryOperator;
+use crate::expressions::simplify::LimitExceeded;
+
+/// The most atomic values an expression can have to be minimized with Espresso, one for each bit of a [`Cube`].
+pub const MAX_VARIABLES: usize = u64::BITS as usize;
+
+/// The most cubes a cover can have while it is built from the covers of the operands,
+/// since a cover can grow exponentially, e.g. for a chain of exclusive or.
+pub const MAX_COVER_CUBES: usize = 4_096;
+
+/// The covers that have been built, by the address of the expression and the value it has in the cover.
+type Covers = HashMap<(*const Expression, bool), Vec<Cube>>;
+
+impl Expression {
+    /// Finds a small, but not necessarily minimal, sum of products that is equivalent to the expression,
+    /// except in the `dont_cares`, using the Espresso heuristic.
+    /// Unlike [`Expression::minimal_sum_of_products`], the truth table is never evaluated,
+    /// so this is feasible for expressions with many atomic values.
+    /// Returns an error if the expression has more than [`MAX_VARIABLES`] atomic values,
+    /// or a cover would have more than [`MAX_COVER_CUBES`] cubes.
+    pub fn espresso(&self, dont_cares: &DontCares) -> Result<Self, LimitExceeded> {
+        let mut variables = self.get_atomic_values().into_iter().collect::<Vec<_>>();
+        if variables.len() > MAX_VARIABLES {
+            return Err(LimitExceeded::AtomicValues { limit: MAX_VARIABLES });
+        }
+        variables.sort();
+        let bits = variables.iter()
+            .enumerate()
+            .map(|(index, name)| (name.as_str(), 1 << (variables.len() - 1 - index)))
+            .collect::<HashMap<_, _>>();
+        let too_many_cubes = LimitExceeded::Cubes { limit: MAX_COVER_CUBES };
+        let on_set = cover(self, &bits, true, &mut Covers::new()).ok_or(too_many_cubes)?;
+        let mut dont_care_set = dont_cares.minterms.iter()
+            .map(|minterm| Cube::minterm(*minterm, variables.len()))
+            .collect::<Vec<_>>();
+        if let Some(expression) = &dont_cares.expression {
+            let cover = cover(expression, &bits, true, &mut Covers::new()).ok_or(too_many_cubes)?;
+            dont_care_set = union(dont_care_set, cover).ok_or(too_many_cubes)?;
+        }
+        Ok(sum_of_products(&minimize(on_set, &dont_care_set), &variables))
+    }
+}
+
+/// Builds a cover of the minterms where the expression has the value `positive`.
+/// The cover is built from the covers of the operands, without evaluating the truth table.
+/// Exclusive or and biconditional need both polarities of their operands, so every cover is only built once.
+/// Returns `None` if a cover would have more than [`MAX_COVER_CUBES`] cubes.
+fn cover(expression: &Expression, bits: &HashMap<&str, u64>, positive: bool, covers: &mut Covers) -> Option<Vec<Cube>> {
+    let key = (std::ptr::from_ref(expression), positive);
+    if let Some(cover) = covers.get(&key) {
+        return Some(cover.clone());
+    }
+    let result = match expression {
+        Expression::Atomic(name) => {
+            let bit = bits[name.as_str()];
+            vec![Cube { value: if positive { bit } else { 0 }, mask: bit }]
+        }
+        Expression::Constant(value) if *value == positive => vec![Cube::TRUE],
+        Expression::Constant(_) => vec![],
+        Expression::Not(expr) => cover(expr, bits, !positive, covers)?,
+        Expression::Binary { left, operator, right } => {
+            let left = |positive, covers: &mut Covers| cover(left, bits, positive, covers);
+            let right = |positive, covers: &mut Covers| cover(right, bits, positive, covers);
+            match (operator, positive) {
+                (BinaryOperator::And, true) | (BinaryOperator::Nand, false) => {
+                    product(&left(true, covers)?, &right(true, covers)?)?
+                }
+                (BinaryOperator::And, false) | (BinaryOperator::Nand, true) => union(left(false, covers)?, right(false, covers)?)?,
+                (BinaryOperator::Or, true) | (BinaryOperator::Nor, false) => union(left(true, covers)?, right(true, covers)?)?,
+                (BinaryOperator::Or, false) | (BinaryOperator::Nor, true) => {
+                    product(&left(false, covers)?, &right(false, covers)?)?
+                }
+                (BinaryOperator::Implication, true) => union(left(false, covers)?, right(true, covers)?)?,
+                (BinaryOperator::Implication, false) => product(&left(true, covers)?, &right(false, covers)?)?,
+                (BinaryOperator::Xor | BinaryOperator::Biconditional, _) => {
+                    let (left_true, left_false) = (left(true, covers)?, left(false, covers)?);
+                    let (right_true, right_false) = (right(true, covers)?, right(false, covers)?);
+                    if (*operator == BinaryOperator::Xor) == positive {
+                        union(product(&left_true, &right_false)?, product(&left_false, &right_true)?)?
+                    } else {
+                        union(product(&left_true, &right_true)?, product(&left_false, &right_false)?)?
+                    }
+                }
+            }
+        }
+    };
+    covers.insert(key, result.clone());
+    Some(result)
+}
+
+/// Returns `None` if there are more than [`MAX_COVER_CUBES`] intersecting pairs of cubes.
+fn product(left: &[Cube], right: &[Cube]) -> Option<Vec<Cube>> {
+    let cover = left.iter()
+        .flat_map(|left| right.iter().filter_map(|right| left.intersect(right)))
+        .take(MAX_COVER_CUBES + 1)
+        .collect::<Vec<_>>();
+    (cover.len() <= MAX_COVER_CUBES).then(|| remove_contained(cover))
+}
+
+/// Returns `None` if there are more than [`MAX_COVER_CUBES`] cubes in both covers together.
+fn union(mut left: Vec<Cube>, right: Vec<Cube>) -> Option<Vec<Cube>> {
+    if left.len() + right.len() > MAX_COVER_CUBES {
+        return None;
+    }
+    left.extend(right);
+    Some(remove_contained(left))
+}
+
+/// Removes the cubes that are contained in another cube of the cover.
+fn remove_contained(mut cover: Vec<Cube>) -> Vec<Cube> {
+    cover.sort_by_key(Cube::literals);
+    cover.dedup();
+    let mut result: Vec<Cube> = vec![];
+    for cube in cover {
+        if !result.iter().any(|other| other.contains(&cube)) {
+            result.push(cube);
+        }
+    }
+    result
+}
+
+/// Minimizes a cover by repeatedly expanding the cubes, removing redundant cubes, and reducing the cubes again,
+/// until the number of cubes and literals stops decreasing.
+/// The minterms in `dont_cares` may be covered, but do not have to be.
+fn minimize(cover: Vec<Cube>, dont_cares: &[Cube]) -> Vec<Cube> {
+    let mut cover = irredundant(expand(cover, dont_cares), dont_cares);
+    loop {
+        let next = irredundant(expand(reduce(cover.clone(), dont_cares), dont_cares), dont_cares);
+        if cost(&next) >= cost(&cover) {
+            return cover;
+        }
+        cover = next;
+    }
+}
+
+/// The number of cubes, and then the number of literals.
+fn cost(cover: &[Cube]) -> (usize, u32) {
+    (cover.len(), cover.iter().map(Cube::literals).sum())
+}
+
+/// Removes as many literals as possible from every cube, while the cube stays inside the function and don't-cares.
+/// Cubes that end up inside an expanded cube are removed.
+fn expand(mut cover: Vec<Cube>, dont_cares: &[Cube]) -> Vec<Cube> {
+    cover.sort_by_key(Cube::literals);
+    let function = [cover.as_slice(), dont_cares].concat();
+    let mut result: Vec<Cube> = vec![];
+    for cube in &cover {
+        if result.iter().any(|other| other.contains(cube)) {
+            continue;
+        }
+        let mut expanded = *cube;
+        for bit in bits(cube.mask) {
+            let candidate = Cube { value: expanded.value & !bit, mask: expanded.mask & !bit };
+            if is_covered(&candidate, &function) {
+                expanded = candidate;
+            }
+        }
+        result.retain(|other| !expanded.contains(other));
+        result.push(expanded);
+    }
+    result
+}
+
+/// Removes cubes that are covered by the rest of the cover and the don't-cares,
+/// starting with the cubes with the most literals.
+fn irredundant(mut cover: Vec<Cube>, dont_cares: &[Cube]) -> Vec<Cube> {
+    cover.sort_by_key(|cube| Reverse(cube.literals()));
+    let mut index = 0;
+    while index < cover.len() {
+        let cube = cover.remove(index);
+        if !is_covered(&cube, &[cover.as_slice(), dont_cares].concat()) {
+            cover.insert(index, cube);
+            index += 1;
+        }
+    }
+    cover
+}
+
+/// Shrinks every cube to the smallest cube containing the minterms that no other cube or don't-care covers,
+/// so the next expansion can move in a different direction.
+fn reduce(cover: Vec<Cube>, dont_cares: &[Cube]) -> Vec<Cube> {
+    let mut cover = cover.into_iter().map(Some).collect::<Vec<_>>();
+    for index in 0..cover.len() {
+        let Some(cube) = cover[index] else {
+            continue;
+        };
+        let rest = cover.iter()
+            .enumerate()
+            .filter(|(other, _)| *other != index)
+            .filter_map(|(_, cube)| *cube)
+            .chain(dont_cares.iter().copied())
+            .collect::<Vec<_>>();
+        cover[index] = complement(&cofactor(&rest, &cube)).into_iter()
+            .reduce(|left, right| left.supercube(&right))
+            .and_then(|supercube| supercube.intersect(&cube));
+    }
+    cover.into_iter().flatten().collect()
+}
+
+/// Whether every minterm of the cube is covered by the cover.
+fn is_covered(cube: &Cube, cover: &[Cube]) -> bool {
+    is_tautology(&cofactor(cover, cube))
+}
+
+/// The part of the cover inside the cube, with the variables of the cube removed.
+fn cofactor(cover: &[Cube], cube: &Cube) -> Vec<Cube> {
+    cover.iter()
+        .filter(|other| other.intersect(cube).is_some())
+        .map(|other| Cube { value: other.value & !cube.mask, mask: other.mask & !cube.mask })
+        .collect()
+}
+
+/// Whether the cover is always true, found by splitting on variables that appear both negated and not negated.
+/// Cubes using a variable that only appears with one sign can be removed, as they never cover the minterms
+/// where that variable has the other value, and a cover without such variables
+/// is only a tautology if it contains the empty cube.
+fn is_tautology(cover: &[Cube]) -> bool {
+    if cover.contains(&Cube::TRUE) {
+        return true;
+    }
+    let positive = cover.iter().fold(0, |bits, cube| bits | cube.mask & cube.value);
+    let negative = cover.iter().fold(0, |bits, cube| bits | cube.mask & !cube.value);
+    let unate = positive ^ negative;
+    if unate != 0 {
+        let reduced = cover.iter()
+            .filter(|cube| cube.mask & unate == 0)
+            .copied()
+            .collect::<Vec<_>>();
+        return is_tautology(&reduced);
+    }
+    // Splitting on the variables of the smallest cubes leads to empty cubes the fastest
+    let fewest_literals = cover.iter().map(Cube::literals).min().unwrap_or_default();
+    let smallest = cover.iter()
+        .filter(|cube| cube.literals() == fewest_literals)
+        .fold(0, |bits, cube| bits | cube.mask);
+    let Some(bit) = most_frequent(cover, positive & negative & smallest) else {
+        return false;
+    };
+    [bit, 0].into_iter().all(|value| is_tautology(&cofactor(cover, &Cube { value, mask: bit })))
+}
+
+/// The minterms that are not covered by the cover, found by splitting on the most frequent variable.
+fn complement(cover: &[Cube]) -> Vec<Cube> {
+    if cover.contains(&Cube::TRUE) {
+        return vec![];
+    }
+    if let [cube] = cover {
+        return bits(cube.mask).map(|bit| Cube { value: !cube.value & bit, mask: bit }).collect();
+    }
+    let used = cover.iter().fold(0, |bits, cube| bits | cube.mask);
+    let Some(bit) = most_frequent(cover, used) else {
+        return vec![Cube::TRUE];
+    };
+    let result = [bit, 0].into_iter()
+        .flat_map(|value| {
+            let literal = Cube { value, mask: bit };
+            complement(&cofactor(cover, &literal)).into_iter()
+                .filter_map(move |cube| cube.intersect(&literal))
+        })
+        .collect();
+    remove_contained(result)
+}
+
+/// The bit among `candidates` that is used by the most cubes.
+fn most_frequent(cover: &[Cube], candidates: u64) -> Option<u64> {
+    bits(candidates).max_by_key(|bit| cover.iter().filter(|cube| cube.mask & bit != 0).count())
+}
+
+/// The bits set in `mask`, from most to least significant.
+fn bits(mask: u64) -> impl Iterator<Item=u64> {
+    (0..u64::BITS).rev()
+        .map(|shift| 1 << shift)
+        .filter(move |bit| mask & bit != 0)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::expressions::expression::Expression;
+    use crate::expressions::helpers::{and, atomic, chain, constant, iff, implies, nand, not, or, xor};
+    use crate::expressions::minimize::DontCares;
+    use crate::expressions::operator::BinaryOperator;
+    use crate::expressions::espresso::MAX_COVER_CUBES;
+    use crate::expressions::simplify::LimitExceeded;
+    use crate::expressions::truth_table::TruthTable;
+
+    fn assert_equivalent(left: &Expression, right: &Expression) {
+        let mut variables = left.get_atomic_values().into_iter().collect::<Vec<_>>();
+        variables.sort();
+        assert_eq!(TruthTable::minterms(left, &variables), TruthTable::minterms(right, &variables), "{left} and {right} differ");
+    }
+
+    #[test]
+    fn test_espresso() {
+        let expression = or(and(atomic("a"), atomic("b")), and(atomic("a"), not(atomic("b"))));
+        assert_eq!(expression.espresso(&DontCares::default()).unwrap(), atomic("a"));
+    }
+
+    #[test]
+    fn test_espresso_consensus() {
+        let expression = or(or(and(atomic("a"), atomic("b")), and(not(atomic("a")), atomic("c"))), and(atomic("b"), atomic("c")));
+        assert_eq!(expression.espresso(&DontCares::default()).unwrap().to_string(), "a ⋀ b ⋁ ¬a ⋀ c");
+    }
+
+    #[test]
+    fn test_espresso_constants() {
+        assert_eq!(or(atomic("a"), not(atomic("a"))).espresso(&DontCares::default()).unwrap(), constant(true));
+        assert_eq!(iff(atomic("a"), not(atomic("a"))).espresso(&DontCares::default()).unwrap(), constant(false));
+    }
+
+    #[test]
+    fn test_espresso_is_equivalent() {
+        let expressions = [
+            xor(xor(atomic("a"), atomic("b")), atomic("c")),
+            implies(or(atomic("a"), atomic("b")), and(atomic("c"), not(atomic("d")))),
+            nand(iff(atomic("a"), atomic("b")), or(atomic("b"), not(atomic("c")))),
+            and(or(atomic("a"), atomic("b")), or(not(atomic("a")), atomic("c"))),
+        ];
+        for expression in expressions {
+            assert_equivalent(&expression, &expression.espresso(&DontCares::default()).unwrap());
+        }
+    }
+
+    #[test]
+    fn test_espresso_matches_minimal_sum_of_products() {
+        let expression = or(iff(atomic("b"), atomic("d")), and(atomic("a"), and(atomic("b"), atomic("d"))));
+        assert_eq!(expression.espresso(&DontCares::default()).unwrap(), expression.minimal_sum_of_products(&DontCares::default()).unwrap());
+        assert_eq!(expression.espresso(&DontCares::default()).unwrap().to_string(), "b ⋀ d ⋁ ¬b ⋀ ¬d");
+    }
+
+    #[test]
+    fn test_espresso_dont_cares() {
+        let expression = and(not(atomic("a")), atomic("c"));
+        let dont_cares = DontCares { minterms: vec![3], expression: None };
+        assert_eq!(expression.espresso(&dont_cares).unwrap(), atomic("c"));
+        let dont_cares = DontCares { minterms: vec![], expression: Some(atomic("a")) };
+        assert_eq!(expression.espresso(&dont_cares).unwrap(), atomic("c"));
+    }
+
+    #[test]
+    fn test_espresso_many_variables() {
+        // 40 atomic values, the truth table would have 2^40 rows
+        let terms = (0..20).map(|index| {
+            let x = atomic(format!("x{index:02}"));
+            let y = atomic(format!("y{index:02}"));
+            or(and(x.clone(), y.clone()), and(x, not(y)))
+        });
+        let expression = chain(BinaryOperator::Or, terms).unwrap();
+        let expected = chain(BinaryOperator::Or, (0..20).map(|index| atomic(format!("x{index:02}")))).unwrap();
+        assert_eq!(expression.espresso(&DontCares::default()).unwrap(), expected);
+    }
+
+    #[test]
+    fn test_espresso_too_many_variables() {
+        let expression = chain(BinaryOperator::And, (0..65).map(|index| atomic(format!("x{index:02}")))).unwrap();
+        assert_eq!(expression.espresso(&DontCares::default()), Err(LimitExceeded::AtomicValues { limit: 64 }));
+    }
+
+    #[test]
+    fn test_espresso_too_many_cubes() {
+        let expression = chain(BinaryOperator::Xor, (0..30).map(|index| atomic(format!("x{index:02}")))).unwrap();
+        assert_eq!(expression.espresso(&DontCares::default()), Err(LimitExceeded::Cubes { limit: MAX_COVER_CUBES }));
+        let clauses = (0..20).map(|index| or(atomic(format!("a{index:02}")), atomic(format!("b{index:02}"))));
+        let expression = chain(BinaryOperator::And, clauses).unwrap();
+        assert_eq!(expression.espresso(&DontCares::default()), Err(LimitExceeded::Cubes { limit: MAX_COVER_CUBES }));
+    }
+}
@@ -1,10 +1,13 @@
+use std::cmp::Ordering;
 use std::collections::HashSet;
 use std::fmt::Display;
+use std::ops::Deref;
 use std::rc::Rc;
 
 use serde::{Deserialize, Serialize};
 
 use crate::expressions::operator::BinaryOperator;
+use crate::parsing::error::ParseError;
 use crate::parsing::expression_parser::parse_expression;
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Deserialize, Serialize)]
@@ -13,6 +16,7 @@ pub enum Expression {
     Not(Rc<Expression>),
     Binary { left: Rc<Expression>, operator: BinaryOperator, right: Rc<Expression> },
     Atomic(String),
+    Constant(bool),
 }
 
 impl Expression {
@@ -20,7 +24,7 @@ impl Expression {
         match self {
             Expression::Not(expr) => expr.is_atomic(),
             Expression::Binary { .. } => false,
-            Expression::Atomic(_) => true
+            Expression::Atomic(_) | Expression::Constant(_) => true
         }
     }
 
@@ -32,18 +36,51 @@ impl Expression {
                 values.extend(right.get_atomic_values());
                 values
             }
-            Expression::Atomic(value) => HashSet::from([value.clone()])
+            Expression::Atomic(value) => HashSet::from([value.clone()]),
+            Expression::Constant(_) => HashSet::new(),
         }
     }
 
+    /// Collects the operands of a chain of the same binary operator, regardless of how the chain is grouped.
+    /// Both `(a ⋀ b) ⋀ c` and `a ⋀ (b ⋀ c)` yield `[a, b, c]` for `BinaryOperator::And`.
+    /// Any expression that is not a binary expression with the given operator is returned as the only operand.
+    pub fn operands(&self, operator: BinaryOperator) -> Vec<&Expression> {
+        match self {
+            Expression::Binary { left, operator: op, right } if *op == operator => {
+                let mut operands = left.operands(operator);
+                operands.extend(right.operands(operator));
+                operands
+            }
+            expression => vec![expression],
+        }
+    }
+
+    /// Compares two expressions, where the order of the operands of commutative operators does not matter.
+    /// `a ⋀ b` is equal to `b ⋀ a`, and `a ⋁ (b ⋁ c)` is equal to `(c ⋁ b) ⋁ a`.
     pub fn eq(&self, other: &Self, ignore_case: bool) -> bool {
         match (self, other) {
             (Expression::Not(left), Expression::Not(right)) => Expression::eq(left, right, ignore_case),
+            (Expression::Binary { operator: left_operator, .. }, Expression::Binary { operator: right_operator, .. })
+            if left_operator == right_operator && left_operator.is_commutative() && left_operator.is_associative() => {
+                let left_operands = self.operands(*left_operator);
+                let mut right_operands = other.operands(*right_operator);
+                left_operands.len() == right_operands.len() && left_operands.into_iter().all(|left| {
+                    if let Some(index) = right_operands.iter().position(|right| Expression::eq(left, right, ignore_case)) {
+                        right_operands.swap_remove(index);
+                        true
+                    } else {
+                        false
+                    }
+                })
+            }
             (Expression::Binary { left: left_left, operator: left_operator, right: left_right },
                 Expression::Binary { left: right_left, operator: right_operator, right: right_right }) => {
-                Expression::eq(left_left, right_left, ignore_case)
-                    && left_operator == right_operator
-                    && Expression::eq(left_right, right_right, ignore_case)
+                left_operator == right_operator && (
+                    Expression::eq(left_left, right_left, ignore_case) && Expression::eq(left_right, right_right, ignore_case)
+                        || left_operator.is_commutative()
+                        && Expression::eq(left_left, right_right, ignore_case)
+                        && Expression::eq(left_right, right_left, ignore_case)
+                )
             }
             (Expression::Atomic(left), Expression::Atomic(right)) => {
                 if ignore_case {
@@ -52,10 +89,37 @@ impl Expression {
                     left == right
                 }
             }
+            (Expression::Constant(left), Expression::Constant(right)) => left == right,
             _ => false
         }
     }
 
+    /// Orders expressions in a deterministic canonical order, used to sort the operands of commutative operators.
+    /// Literals come first, ordered by name with `a` before `¬a`,
+    /// followed by the remaining expressions ordered by their string representation.
+    pub fn canonical_cmp(&self, other: &Self) -> Ordering {
+        match (self.as_literal(), other.as_literal()) {
+            (Some((left, left_negated)), Some((right, right_negated))) => {
+                left.cmp(right).then(left_negated.cmp(&right_negated))
+            }
+            (Some(_), None) => Ordering::Less,
+            (None, Some(_)) => Ordering::Greater,
+            (None, None) => self.to_string().cmp(&other.to_string()),
+        }
+    }
+
+    /// Returns the name of the atomic value, and whether it is negated, if the expression is `a` or `¬a`.
+    pub fn as_literal(&self) -> Option<(&str, bool)> {
+        match self {
+            Expression::Atomic(value) => Some((value, false)),
+            Expression::Not(expr) => match expr.deref() {
+                Expression::Atomic(value) => Some((value, true)),
+                _ => None,
+            },
+            Expression::Binary { .. } | Expression::Constant(_) => None,
+        }
+    }
+
     pub fn opposite_eq(&self, other: &Self, ignore_case: bool) -> bool {
         match (self, other) {
             (Expression::Not(_), Expression::Not(_)) => false,
@@ -66,52 +130,144 @@ impl Expression {
     }
 }
 
-impl<'a> TryFrom<&'a str> for Expression {
-    type Error = nom::Err<nom::error::Error<&'a str>>;
-    fn try_from(value: &'a str) -> Result<Self, Self::Error> {
+impl TryFrom<&str> for Expression {
+    type Error = ParseError;
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
         parse_expression(value)
     }
 }
 
 impl TryFrom<String> for Expression {
-    type Error = nom::Err<nom::error::Error<String>>;
+    type Error = ParseError;
     fn try_from(value: String) -> Result<Self, Self::Error> {
         Self::try_from(value.as_str())
-            .map_err(|err| err.map(|err| nom::error::Error::new(err.input.into(), err.code)))
     }
 }
 
-impl Display for Expression {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        return write!(f, "{}", fmt_helper(self, None));
+/// The symbols an expression is written with.
+pub(super) struct Symbols {
+    /// Written directly in front of the operand.
+    pub not: &'static str,
+    pub and: &'static str,
+    pub or: &'static str,
+    pub implication: &'static str,
+    pub biconditional: &'static str,
+    pub xor: &'static str,
+    pub nand: &'static str,
+    pub nor: &'static str,
+    pub truth: &'static str,
+    pub falsehood: &'static str,
+    pub atomic: fn(&str) -> String,
+}
+
+impl Symbols {
+    fn binary(&self, operator: BinaryOperator) -> &'static str {
+        match operator {
+            BinaryOperator::And => self.and,
+            BinaryOperator::Or => self.or,
+            BinaryOperator::Implication => self.implication,
+            BinaryOperator::Biconditional => self.biconditional,
+            BinaryOperator::Xor => self.xor,
+            BinaryOperator::Nand => self.nand,
+            BinaryOperator::Nor => self.nor,
+        }
+    }
+}
 
-        fn fmt_helper(expression: &Expression, parent: Option<&Expression>) -> String {
+/// The symbols used by [`Display`].
+pub(super) const UNICODE: Symbols = Symbols {
+    not: "¬",
+    and: BinaryOperator::And.symbol(),
+    or: BinaryOperator::Or.symbol(),
+    implication: BinaryOperator::Implication.symbol(),
+    biconditional: BinaryOperator::Biconditional.symbol(),
+    xor: BinaryOperator::Xor.symbol(),
+    nand: BinaryOperator::Nand.symbol(),
+    nor: BinaryOperator::Nor.symbol(),
+    truth: "⊤",
+    falsehood: "⊥",
+    atomic: str::to_string,
+};
+
+impl Expression {
+    /// Writes the expression with the given symbols, as used by [`Display`].
+    /// Only the parentheses needed to parse the same expression again are written.
+    pub(super) fn format(&self, symbols: &Symbols) -> String {
+        return fmt_helper(self, symbols);
+
+        fn fmt_helper(expression: &Expression, symbols: &Symbols) -> String {
             match expression {
-                Expression::Not(expr) if expr.is_atomic() => format!("¬{}", fmt_helper(expr, Some(expression))),
-                Expression::Not(expr) => format!("¬({})", fmt_helper(expr, Some(expression))),
-                Expression::Binary { left, operator: BinaryOperator::And, right } => {
-                    format!("{} ⋀ {}", fmt_helper(left, Some(expression)), fmt_helper(right, Some(expression)))
-                }
-                Expression::Binary { left, operator: BinaryOperator::Or, right } => {
-                    if parent.is_none() || matches!(parent, Some(Expression::Binary { operator: BinaryOperator::Or, .. })) {
-                        format!("{} ⋁ {}", fmt_helper(left, Some(expression)), fmt_helper(right, Some(expression)))
-                    } else {
-                        format!("({} ⋁ {})", fmt_helper(left, Some(expression)), fmt_helper(right, Some(expression)))
-                    }
-                }
-                Expression::Binary { left, operator: BinaryOperator::Implication, right } => {
-                    format!("{} ➔ {}", fmt_helper(left, Some(expression)), fmt_helper(right, Some(expression)))
+                Expression::Not(expr) if matches!(expr.as_ref(), Expression::Binary { .. }) => {
+                    format!("{}({})", symbols.not, fmt_helper(expr, symbols))
                 }
-                Expression::Atomic(value) => value.clone(),
+                Expression::Not(expr) => format!("{}{}", symbols.not, fmt_helper(expr, symbols)),
+                Expression::Binary { left, operator, right } => format!(
+                    "{} {} {}",
+                    operand(left, *operator, false, symbols),
+                    symbols.binary(*operator),
+                    operand(right, *operator, true, symbols),
+                ),
+                Expression::Atomic(value) => (symbols.atomic)(value),
+                Expression::Constant(true) => symbols.truth.into(),
+                Expression::Constant(false) => symbols.falsehood.into(),
             }
         }
+
+        /// Operands that bind less tightly than the parent are parenthesized.
+        /// Operators with the same precedence are parsed left associative, so an operand on the right with the same precedence is parenthesized too.
+        fn operand(operand: &Expression, parent: BinaryOperator, is_right: bool, symbols: &Symbols) -> String {
+            let written = fmt_helper(operand, symbols);
+            match operand {
+                Expression::Binary { operator, .. } if operator.precedence() < parent.precedence()
+                    || is_right && operator.precedence() == parent.precedence() => format!("({written})"),
+                _ => written,
+            }
+        }
+    }
+}
+
+impl Display for Expression {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.format(&UNICODE))
     }
 }
 
 #[cfg(test)]
 mod tests {
     use crate::expressions::expression::Expression;
-    use crate::expressions::helpers::{and, atomic, implies, not, or};
+    use crate::expressions::helpers::{and, atomic, binary, constant, iff, implies, nand, nor, not, or, xor};
+    use crate::expressions::operator::BinaryOperator;
+    use crate::parsing::expression_parser::parse_expression;
+
+    /// A xorshift generator, so the property tests are reproducible without any dependencies.
+    struct Random(u64);
+
+    impl Random {
+        fn next(&mut self, bound: u64) -> u64 {
+            self.0 ^= self.0 << 13;
+            self.0 ^= self.0 >> 7;
+            self.0 ^= self.0 << 17;
+            self.0 % bound
+        }
+
+        fn expression(&mut self, depth: u32) -> Expression {
+            const OPERATORS: [BinaryOperator; 7] = [
+                BinaryOperator::Biconditional,
+                BinaryOperator::Implication,
+                BinaryOperator::Or,
+                BinaryOperator::Nor,
+                BinaryOperator::Xor,
+                BinaryOperator::And,
+                BinaryOperator::Nand,
+            ];
+            match self.next(if depth == 0 { 2 } else { 6 }) {
+                0 => atomic(["a", "b", "c"][self.next(3) as usize]),
+                1 => constant(self.next(2) == 0),
+                2 => not(self.expression(depth - 1)),
+                _ => binary(self.expression(depth - 1), OPERATORS[self.next(7) as usize], self.expression(depth - 1)),
+            }
+        }
+    }
 
     #[test]
     fn test_eq_ignore_case_atomics() {
@@ -155,6 +311,41 @@ mod tests {
         assert!(!expression_lower.eq(&expression_upper, false));
     }
 
+    #[test]
+    fn test_eq_commutative_and() {
+        let expression = and(atomic("a"), atomic("b"));
+        let other = and(atomic("b"), atomic("a"));
+        assert!(expression.eq(&other, false));
+    }
+
+    #[test]
+    fn test_eq_commutative_nested_or() {
+        let expression = or(atomic("a"), or(atomic("b"), atomic("c")));
+        let other = or(or(atomic("c"), atomic("b")), atomic("a"));
+        assert!(expression.eq(&other, false));
+    }
+
+    #[test]
+    fn test_eq_commutative_different_count() {
+        let expression = and(and(atomic("a"), atomic("a")), atomic("b"));
+        let other = and(and(atomic("a"), atomic("b")), atomic("b"));
+        assert!(!expression.eq(&other, false));
+    }
+
+    #[test]
+    fn test_eq_implication_not_commutative() {
+        let expression = implies(atomic("a"), atomic("b"));
+        let other = implies(atomic("b"), atomic("a"));
+        assert!(!expression.eq(&other, false));
+    }
+
+    #[test]
+    fn test_canonical_cmp_literals_before_compound() {
+        let mut expressions = vec![and(atomic("a"), atomic("b")), not(atomic("a")), atomic("b"), atomic("a")];
+        expressions.sort_by(Expression::canonical_cmp);
+        assert_eq!(expressions, vec![atomic("a"), not(atomic("a")), atomic("b"), and(atomic("a"), atomic("b"))]);
+    }
+
     #[test]
     fn test_expression_a_and_not_b_display() {
         let expression = and(
@@ -193,7 +384,7 @@ mod tests {
                 atomic("c"),
             ),
         );
-        assert_eq!(expression.to_string(), "a ⋁ b ⋁ c");
+        assert_eq!(expression.to_string(), "a ⋁ (b ⋁ c)");
     }
 
     #[test]
@@ -208,7 +399,7 @@ mod tests {
                 ),
             ),
         );
-        assert_eq!(expression.to_string(), "a ⋁ b ⋁ c ⋁ d");
+        assert_eq!(expression.to_string(), "a ⋁ (b ⋁ (c ⋁ d))");
     }
 
     #[test]
@@ -247,6 +438,57 @@ mod tests {
         assert_eq!(expression.to_string(), "a ➔ b");
     }
 
+    #[test]
+    fn test_expression_a_iff_b_display() {
+        let expression = iff(atomic("a"), atomic("b"));
+        assert_eq!(expression.to_string(), "a ⇔ b");
+    }
+
+    #[test]
+    fn test_expression_nested_iff_display() {
+        let expression = and(iff(atomic("a"), atomic("b")), atomic("c"));
+        assert_eq!(expression.to_string(), "(a ⇔ b) ⋀ c");
+    }
+
+    #[test]
+    fn test_eq_commutative_iff() {
+        let expression = iff(atomic("a"), not(atomic("b")));
+        let other = iff(not(atomic("b")), atomic("a"));
+        assert!(expression.eq(&other, false));
+    }
+
+    #[test]
+    fn test_expression_xor_chain_display() {
+        let expression = xor(atomic("a"), xor(atomic("b"), atomic("c")));
+        assert_eq!(expression.to_string(), "a ⊕ (b ⊕ c)");
+    }
+
+    #[test]
+    fn test_expression_nested_nand_display() {
+        let expression = nand(nand(atomic("a"), atomic("b")), atomic("c"));
+        assert_eq!(expression.to_string(), "a ↑ b ↑ c");
+    }
+
+    #[test]
+    fn test_expression_nor_display() {
+        let expression = nor(atomic("a"), not(atomic("b")));
+        assert_eq!(expression.to_string(), "a ↓ ¬b");
+    }
+
+    #[test]
+    fn test_eq_commutative_nand() {
+        let expression = nand(atomic("a"), atomic("b"));
+        let other = nand(atomic("b"), atomic("a"));
+        assert!(expression.eq(&other, false));
+    }
+
+    #[test]
+    fn test_eq_nand_not_associative() {
+        let expression = nand(nand(atomic("a"), atomic("b")), atomic("c"));
+        let other = nand(atomic("a"), nand(atomic("b"), atomic("c")));
+        assert!(!expression.eq(&other, false));
+    }
+
     #[test]
     fn test_expression_not_a_and_b_display() {
         let expression = not(and(
@@ -256,6 +498,19 @@ mod tests {
         assert_eq!(expression.to_string(), "¬(a ⋀ b)");
     }
 
+    #[test]
+    fn test_expression_constants_display() {
+        let expression = or(and(atomic("a"), constant(true)), not(constant(false)));
+        assert_eq!(expression.to_string(), "a ⋀ ⊤ ⋁ ¬⊥");
+    }
+
+    #[test]
+    fn test_eq_constants() {
+        assert!(constant(true).eq(&constant(true), false));
+        assert!(!constant(true).eq(&constant(false), false));
+        assert!(!constant(true).eq(&atomic("true"), true));
+    }
+
     #[test]
     fn test_from_str_into_expression_atomic() {
         let expression: Expression = "a".try_into().unwrap();
@@ -286,6 +541,12 @@ mod tests {
         assert_eq!(expression, implies(atomic("a"), atomic("b")));
     }
 
+    #[test]
+    fn test_from_str_into_expression_iff() {
+        let expression: Expression = "a <=> b".try_into().unwrap();
+        assert_eq!(expression, iff(atomic("a"), atomic("b")));
+    }
+
     #[test]
     fn test_from_str_into_expression_complex() {
         let expression: Expression = "a & b | c".try_into().unwrap();
@@ -308,4 +569,57 @@ mod tests {
     fn test_from_str_into_expression_empty() {
         assert!(Expression::try_from("").is_err());
     }
+
+    #[test]
+    fn test_display_implication_chain() {
+        assert_eq!(implies(implies(atomic("a"), atomic("b")), atomic("c")).to_string(), "a ➔ b ➔ c");
+        assert_eq!(implies(atomic("a"), implies(atomic("b"), atomic("c"))).to_string(), "a ➔ (b ➔ c)");
+    }
+
+    #[test]
+    fn test_display_precedence() {
+        assert_eq!(and(atomic("a"), implies(atomic("b"), atomic("c"))).to_string(), "a ⋀ (b ➔ c)");
+        assert_eq!(or(xor(atomic("a"), atomic("b")), and(atomic("c"), atomic("d"))).to_string(), "a ⊕ b ⋁ c ⋀ d");
+        assert_eq!(not(not(and(atomic("a"), atomic("b")))).to_string(), "¬¬(a ⋀ b)");
+    }
+
+    #[test]
+    fn test_display_same_precedence() {
+        assert_eq!(and(and(atomic("a"), atomic("b")), atomic("c")).to_string(), "a ⋀ b ⋀ c");
+        assert_eq!(and(atomic("a"), and(atomic("b"), atomic("c"))).to_string(), "a ⋀ (b ⋀ c)");
+        assert_eq!(nand(and(atomic("a"), atomic("b")), atomic("c")).to_string(), "a ⋀ b ↑ c");
+        assert_eq!(and(atomic("a"), nand(atomic("b"), atomic("c"))).to_string(), "a ⋀ (b ↑ c)");
+        assert_eq!(nor(atomic("a"), nor(atomic("b"), atomic("c"))).to_string(), "a ↓ (b ↓ c)");
+    }
+
+    #[test]
+    fn test_display_round_trip() {
+        let mut random = Random(0x2545_f491_4f6c_dd1d);
+        for _ in 0..2000 {
+            let expression = random.expression(5);
+            let displayed = expression.to_string();
+            assert_eq!(parse_expression(&displayed), Ok(expression), "{displayed}");
+        }
+    }
+
+    #[test]
+    fn test_display_only_needed_parentheses() {
+        let mut random = Random(0x9e37_79b9_7f4a_7c15);
+        for _ in 0..2000 {
+            let expression = random.expression(5);
+            let displayed = expression.to_string();
+            let mut open = vec![];
+            for (index, c) in displayed.char_indices() {
+                match c {
+                    '(' => open.push(index),
+                    ')' => {
+                        let start = open.pop().unwrap();
+                        let removed = format!("{}{}{}", &displayed[..start], &displayed[start + 1..index], &displayed[index + 1..]);
+                        assert_ne!(parse_expression(&removed), Ok(expression.clone()), "{displayed}");
+                    }
+                    _ => {}
+                }
+            }
+        }
+    }
 }
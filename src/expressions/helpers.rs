@@ -29,6 +29,45 @@ where
     binary(left, BinaryOperator::Implication, right)
 }
 
+#[cfg(test)]
+#[inline]
+pub fn iff<L, R>(left: L, right: R) -> Expression
+where
+    L: Into<Rc<Expression>>,
+    R: Into<Rc<Expression>>,
+{
+    binary(left, BinaryOperator::Biconditional, right)
+}
+
+#[inline]
+pub fn xor<L, R>(left: L, right: R) -> Expression
+where
+    L: Into<Rc<Expression>>,
+    R: Into<Rc<Expression>>,
+{
+    binary(left, BinaryOperator::Xor, right)
+}
+
+#[cfg(test)]
+#[inline]
+pub fn nand<L, R>(left: L, right: R) -> Expression
+where
+    L: Into<Rc<Expression>>,
+    R: Into<Rc<Expression>>,
+{
+    binary(left, BinaryOperator::Nand, right)
+}
+
+#[cfg(test)]
+#[inline]
+pub fn nor<L, R>(left: L, right: R) -> Expression
+where
+    L: Into<Rc<Expression>>,
+    R: Into<Rc<Expression>>,
+{
+    binary(left, BinaryOperator::Nor, right)
+}
+
 #[inline]
 pub fn binary<L, R>(left: L, operator: BinaryOperator, right: R) -> Expression
 where
@@ -38,11 +77,25 @@ where
     Expression::Binary { left: left.into(), operator, right: right.into() }
 }
 
+/// Combines the operands into a left associated chain of the given operator, `((a ⋀ b) ⋀ c) ⋀ d`.
+/// Returns `None` if there are no operands.
+pub fn chain<I>(operator: BinaryOperator, operands: I) -> Option<Expression>
+where
+    I: IntoIterator<Item=Expression>,
+{
+    operands.into_iter().reduce(|left, right| binary(left, operator, right))
+}
+
 #[inline]
 pub fn not<T: Into<Rc<Expression>>>(value: T) -> Expression {
     Expression::Not(value.into())
 }
 
+#[inline]
+pub fn constant(value: bool) -> Expression {
+    Expression::Constant(value)
+}
+
 #[inline]
 pub fn atomic<T: Into<String>>(value: T) -> Expression {
     Expression::Atomic(value.into())
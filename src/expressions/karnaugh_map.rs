@@ -0,0 +1,200 @@
+use serde::Serialize;
+
+use crate::expressions::expression::Expression;
+use crate::expressions::minimize::{Cube, minimal_cover, prime_implicants};
+use crate::expressions::truth_table::TruthTable;
+
+/// The fewest atomic values a Karnaugh map can be drawn for.
+pub const MIN_VARIABLES: usize = 2;
+/// The most atomic values a Karnaugh map can be drawn for.
+pub const MAX_VARIABLES: usize = 6;
+
+/// A truth table laid out as a grid, where neighbouring cells differ in the value of a single atomic value.
+/// The rows and columns are in Gray code order, so adjacent minterms can be grouped visually.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct KarnaughMap {
+    /// The atomic values that select the row, where the first is the leftmost digit of a row label.
+    row_variables: Vec<String>,
+    /// The atomic values that select the column, where the first is the leftmost digit of a column label.
+    column_variables: Vec<String>,
+    /// The values of the row variables for each row, e.g. `"01"` is `¬a ⋀ b`.
+    row_labels: Vec<String>,
+    /// The values of the column variables for each column.
+    column_labels: Vec<String>,
+    /// The value of the expression in each cell, indexed by row and then column.
+    grid: Vec<Vec<bool>>,
+    /// The prime implicants of a minimal sum of products, only present if requested.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    groups: Option<Vec<Group>>,
+}
+
+/// A rectangle of true cells, which may wrap around the edges of the map.
+#[derive(Debug, PartialEq, Serialize)]
+pub struct Group {
+    /// The conjunction of literals that is true in exactly these cells.
+    pub term: String,
+    /// The `[row, column]` of every cell in the group.
+    pub cells: Vec<[usize; 2]>,
+}
+
+impl KarnaughMap {
+    /// Lays out the expression as a Karnaugh map, with the atomic values ordered by name.
+    /// If `groups` is true, the groups of a minimal sum of products are included.
+    /// Returns `None` if the expression does not have between [`MIN_VARIABLES`] and [`MAX_VARIABLES`] atomic values,
+    /// or the groups would need too many products in Petrick's method, see [`minimal_cover`].
+    pub fn new(expression: &Expression, groups: bool) -> Option<Self> {
+        let mut variables = expression.get_atomic_values().into_iter().collect::<Vec<_>>();
+        if !(MIN_VARIABLES..=MAX_VARIABLES).contains(&variables.len()) {
+            return None;
+        }
+        variables.sort();
+        let column_variables = variables.split_off(variables.len() / 2);
+        let row_variables = variables;
+        let variables = [row_variables.as_slice(), column_variables.as_slice()].concat();
+
+        let minterms = TruthTable::minterms(expression, &variables);
+        let layout = Layout { rows: row_variables.len(), columns: column_variables.len() };
+        let grid = (0..layout.row_count())
+            .map(|row| (0..layout.column_count())
+                .map(|column| minterms.binary_search(&layout.minterm(row, column)).is_ok())
+                .collect())
+            .collect();
+        let groups = if groups {
+            let primes = prime_implicants(&minterms, variables.len());
+            let cover = minimal_cover(&primes, &minterms)?;
+            Some(cover.into_iter().map(|cube| layout.group(cube, &variables)).collect())
+        } else {
+            None
+        };
+        Some(Self {
+            row_labels: labels(layout.rows),
+            column_labels: labels(layout.columns),
+            row_variables,
+            column_variables,
+            grid,
+            groups,
+        })
+    }
+}
+
+/// The number of variables along each side of the map.
+struct Layout {
+    rows: usize,
+    columns: usize,
+}
+
+impl Layout {
+    const fn row_count(&self) -> usize {
+        1 << self.rows
+    }
+
+    const fn column_count(&self) -> usize {
+        1 << self.columns
+    }
+
+    /// The minterm in the cell, where the row variables are the most significant bits.
+    const fn minterm(&self, row: usize, column: usize) -> u64 {
+        ((gray_code(row) << self.columns) | gray_code(column)) as u64
+    }
+
+    fn group(&self, cube: Cube, variables: &[String]) -> Group {
+        let cells = (0..self.row_count())
+            .flat_map(|row| (0..self.column_count()).map(move |column| [row, column]))
+            .filter(|[row, column]| cube.covers(self.minterm(*row, *column)))
+            .collect();
+        Group { term: cube.to_expression(variables).to_string(), cells }
+    }
+}
+
+/// The values of the variables along one side of the map, in Gray code order.
+fn labels(variables: usize) -> Vec<String> {
+    (0..1 << variables)
+        .map(|index| format!("{:0variables$b}", gray_code(index)))
+        .collect()
+}
+
+/// The `index`th number in a sequence where consecutive numbers differ in exactly one bit.
+const fn gray_code(index: usize) -> usize {
+    index ^ (index >> 1)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::expressions::helpers::{and, atomic, constant, not, or, xor};
+    use crate::expressions::karnaugh_map::{Group, KarnaughMap};
+
+    #[test]
+    fn test_gray_code() {
+        let codes = (0..8).map(super::gray_code).collect::<Vec<_>>();
+        assert_eq!(codes, vec![0, 1, 3, 2, 6, 7, 5, 4]);
+    }
+
+    #[test]
+    fn test_karnaugh_map_two_variables() {
+        let map = KarnaughMap::new(&and(atomic("a"), atomic("b")), false).unwrap();
+        assert_eq!(map.row_variables, vec!["a"]);
+        assert_eq!(map.column_variables, vec!["b"]);
+        assert_eq!(map.row_labels, vec!["0", "1"]);
+        assert_eq!(map.column_labels, vec!["0", "1"]);
+        assert_eq!(map.grid, vec![vec![false, false], vec![false, true]]);
+        assert!(map.groups.is_none());
+    }
+
+    #[test]
+    fn test_karnaugh_map_four_variables() {
+        let expression = or(and(xor(atomic("a"), atomic("b")), atomic("d")), and(atomic("c"), not(atomic("c"))));
+        let map = KarnaughMap::new(&expression, false).unwrap();
+        assert_eq!(map.row_variables, vec!["a", "b"]);
+        assert_eq!(map.column_variables, vec!["c", "d"]);
+        assert_eq!(map.row_labels, vec!["00", "01", "11", "10"]);
+        assert_eq!(map.column_labels, vec!["00", "01", "11", "10"]);
+        assert_eq!(map.grid, vec![
+            vec![false, false, false, false],
+            vec![false, true, true, false],
+            vec![false, false, false, false],
+            vec![false, true, true, false],
+        ]);
+    }
+
+    #[test]
+    fn test_karnaugh_map_shape() {
+        let variables = ["a", "b", "c", "d", "e", "f"].map(atomic);
+        for count in 2..=6 {
+            let expression = variables[1..count].iter().cloned().fold(variables[0].clone(), or);
+            let map = KarnaughMap::new(&expression, false).unwrap();
+            assert_eq!(map.grid.len(), 1 << (count / 2));
+            assert!(map.grid.iter().all(|row| row.len() == 1 << (count - count / 2)));
+        }
+    }
+
+    #[test]
+    fn test_karnaugh_map_groups_wrap_around() {
+        // The corners of a four variable map form a single group
+        let expression = and(not(atomic("b")), not(atomic("d")));
+        let unused = or(and(atomic("a"), not(atomic("a"))), and(atomic("c"), not(atomic("c"))));
+        let map = KarnaughMap::new(&or(expression, unused), true).unwrap();
+        assert_eq!(map.groups, Some(vec![Group {
+            term: "¬b ⋀ ¬d".to_string(),
+            cells: vec![[0, 0], [0, 3], [3, 0], [3, 3]],
+        }]));
+    }
+
+    #[test]
+    fn test_karnaugh_map_groups() {
+        let expression = or(and(atomic("a"), atomic("b")), and(not(atomic("a")), atomic("c")));
+        let map = KarnaughMap::new(&expression, true).unwrap();
+        let mut terms = map.groups.unwrap().into_iter().map(|group| group.term).collect::<Vec<_>>();
+        terms.sort();
+        assert_eq!(terms, vec!["a ⋀ b", "¬a ⋀ c"]);
+    }
+
+    #[test]
+    fn test_karnaugh_map_too_few_or_many_variables() {
+        assert!(KarnaughMap::new(&constant(true), false).is_none());
+        assert!(KarnaughMap::new(&atomic("a"), false).is_none());
+        let variables = ["a", "b", "c", "d", "e", "f", "g"].map(atomic);
+        let expression = variables[1..].iter().cloned().fold(variables[0].clone(), and);
+        assert!(KarnaughMap::new(&expression, false).is_none());
+    }
+}
@@ -0,0 +1,130 @@
+use std::fmt::Write;
+
+use crate::expressions::expression::{Expression, Symbols};
+use crate::routing::response::Operation;
+
+/// The symbols used by [`Expression::to_latex`], which must be written in math mode.
+const LATEX: Symbols = Symbols {
+    not: "\\neg ",
+    and: "\\land",
+    or: "\\lor",
+    implication: "\\to",
+    biconditional: "\\leftrightarrow",
+    xor: "\\oplus",
+    nand: "\\uparrow",
+    nor: "\\downarrow",
+    truth: "\\top",
+    falsehood: "\\bot",
+    atomic: latex_atomic,
+};
+
+impl Expression {
+    /// Writes the expression as LaTeX math, e.g. `a ⋀ ¬b` is written as `a \land \neg b`.
+    pub fn to_latex(&self) -> String {
+        self.format(&LATEX)
+    }
+}
+
+/// Atomic values with a single letter are written as is, while longer names are written upright as one word,
+/// so `ab` is not mistaken for `a` times `b`.
+fn latex_atomic(value: &str) -> String {
+    let value = value.replace('_', "\\_");
+    if value.len() == 1 {
+        value
+    } else {
+        format!("\\mathit{{{value}}}")
+    }
+}
+
+/// Writes the operations as an `align*` environment, with one rewrite and the law that was applied on each line.
+/// If there are no operations, such as for strategies that do not record any steps,
+/// `before` is written as equivalent to `after`, or on its own if they are the same.
+pub fn derivation(before: &Expression, after: &Expression, operations: &[Operation]) -> String {
+    if operations.is_empty() {
+        return if before == after {
+            format!("\\[ {} \\]", before.to_latex())
+        } else {
+            format!("\\[ {} \\equiv {} \\]", before.to_latex(), after.to_latex())
+        };
+    }
+    let mut latex = String::from("\\begin{align*}\n");
+    for (index, operation) in operations.iter().enumerate() {
+        let _ = write!(
+            latex,
+            "  {} &\\equiv {} && \\text{{{}}}",
+            operation.before.to_latex(),
+            operation.after.to_latex(),
+            escape_text(operation.law.name()),
+        );
+        latex.push_str(if index + 1 < operations.len() { " \\\\\n" } else { "\n" });
+    }
+    latex.push_str("\\end{align*}");
+    latex
+}
+
+/// Escapes the characters that have a special meaning in LaTeX text.
+fn escape_text(text: &str) -> String {
+    text.chars()
+        .fold(String::with_capacity(text.len()), |mut escaped, c| {
+            match c {
+                '\\' => escaped.push_str("\\textbackslash{}"),
+                '&' | '%' | '$' | '#' | '_' | '{' | '}' => {
+                    escaped.push('\\');
+                    escaped.push(c);
+                }
+                '~' => escaped.push_str("\\textasciitilde{}"),
+                '^' => escaped.push_str("\\textasciicircum{}"),
+                c => escaped.push(c),
+            }
+            escaped
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::expressions::helpers::{and, atomic, constant, iff, implies, nand, not, or, xor};
+    use crate::expressions::latex::{derivation, escape_text};
+    use crate::expressions::simplify::Law;
+    use crate::routing::response::Operation;
+
+    #[test]
+    fn test_to_latex() {
+        let expression = implies(and(atomic("a"), not(atomic("b"))), or(atomic("c"), constant(false)));
+        assert_eq!(expression.to_latex(), "a \\land \\neg b \\to c \\lor \\bot");
+    }
+
+    #[test]
+    fn test_to_latex_parentheses_match_display() {
+        let expression = iff(xor(not(atomic("a")), atomic("b")), nand(atomic("c"), and(atomic("d"), constant(true))));
+        assert_eq!(expression.to_string(), "¬a ⊕ b ⇔ c ↑ (d ⋀ ⊤)");
+        assert_eq!(expression.to_latex(), "\\neg a \\oplus b \\leftrightarrow c \\uparrow (d \\land \\top)");
+    }
+
+    #[test]
+    fn test_to_latex_long_atomic() {
+        assert_eq!(and(atomic("rain_1"), atomic("x")).to_latex(), "\\mathit{rain\\_1} \\land x");
+    }
+
+    #[test]
+    fn test_derivation() {
+        let before = implies(atomic("a"), atomic("b"));
+        let after = or(not(atomic("a")), atomic("b"));
+        let operation = Operation::new(&before, &after, Law::EliminationOfImplication).unwrap();
+        assert_eq!(derivation(&before, &after, &[operation]), "\\begin{align*}\n  \
+            a \\to b &\\equiv \\neg a \\lor b && \\text{Elimination of implication}\n\
+            \\end{align*}");
+    }
+
+    #[test]
+    fn test_derivation_without_operations() {
+        assert_eq!(derivation(&atomic("a"), &atomic("a"), &[]), "\\[ a \\]");
+        let before = or(and(atomic("a"), atomic("b")), and(atomic("a"), not(atomic("b"))));
+        assert_eq!(derivation(&before, &atomic("a"), &[]), "\\[ a \\land b \\lor a \\land \\neg b \\equiv a \\]");
+    }
+
+    #[test]
+    fn test_escape_text() {
+        assert_eq!(escape_text("De Morgan's laws"), "De Morgan's laws");
+        assert_eq!(escape_text("a_b & {c}"), "a\\_b \\& \\{c\\}");
+    }
+}
@@ -0,0 +1,401 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use crate::expressions::expression::Expression;
+use crate::expressions::helpers::{atomic, chain, not};
+use crate::expressions::operator::BinaryOperator;
+use crate::expressions::simplify::LimitExceeded;
+use crate::expressions::truth_table::TruthTable;
+
+/// The most atomic values an expression can have to find a minimal sum of products,
+/// since the whole truth table is evaluated.
+pub const MAX_VARIABLES: usize = 12;
+
+/// The most products Petrick's method can have while multiplying out the product of sums,
+/// since the number of products is exponential in the number of prime implicants that are not essential.
+pub const MAX_PETRICK_PRODUCTS: usize = 4_096;
+
+/// A conjunction of literals over an ordered list of variables, where the first variable is the most significant bit.
+/// The bits set in `mask` are the variables in the conjunction, and the same bits in `value` are whether they are true.
+/// A cube with every bit set in `mask` is a single minterm, and a cube with an empty `mask` is always true.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Cube {
+    pub value: u64,
+    pub mask: u64,
+}
+
+impl Cube {
+    /// The cube without any literals, which is always true.
+    pub const TRUE: Self = Self { value: 0, mask: 0 };
+
+    pub const fn minterm(index: u64, variables: usize) -> Self {
+        let mask = match u64::MAX.checked_shr(u64::BITS - variables as u32) {
+            Some(mask) => mask,
+            None => 0,
+        };
+        Self { value: index, mask }
+    }
+
+    /// Whether the minterm makes this cube true.
+    pub const fn covers(&self, minterm: u64) -> bool {
+        minterm & self.mask == self.value
+    }
+
+    /// The number of literals in the conjunction.
+    pub const fn literals(&self) -> u32 {
+        self.mask.count_ones()
+    }
+
+    /// Whether every minterm covered by `other` is also covered by this cube.
+    pub const fn contains(&self, other: &Self) -> bool {
+        self.mask & !other.mask == 0 && (self.value ^ other.value) & self.mask == 0
+    }
+
+    /// The conjunction of both cubes, or `None` if they have a variable with opposite values.
+    pub const fn intersect(&self, other: &Self) -> Option<Self> {
+        if (self.value ^ other.value) & self.mask & other.mask != 0 {
+            None
+        } else {
+            Some(Self { value: self.value | other.value, mask: self.mask | other.mask })
+        }
+    }
+
+    /// The smallest cube containing both cubes.
+    pub const fn supercube(&self, other: &Self) -> Self {
+        let mask = self.mask & other.mask & !(self.value ^ other.value);
+        Self { value: self.value & mask, mask }
+    }
+
+    /// Combines two cubes that only differ in the value of one variable, `a ⋀ b` and `a ⋀ ¬b` into `a`.
+    fn combine(&self, other: &Self) -> Option<Self> {
+        let difference = self.value ^ other.value;
+        if self.mask == other.mask && difference.count_ones() == 1 {
+            Some(Self { value: self.value & !difference, mask: self.mask & !difference })
+        } else {
+            None
+        }
+    }
+
+    pub fn to_expression(self, variables: &[String]) -> Expression {
+        let literals = variables.iter().enumerate().filter_map(|(index, name)| {
+            let bit = 1 << (variables.len() - 1 - index);
+            match (self.mask & bit != 0, self.value & bit != 0) {
+                (true, true) => Some(atomic(name.as_str())),
+                (true, false) => Some(not(atomic(name.as_str()))),
+                (false, _) => None,
+            }
+        });
+        chain(BinaryOperator::And, literals).unwrap_or(Expression::Constant(true))
+    }
+}
+
+/// Finds all prime implicants of the minterms using the Quine–McCluskey algorithm.
+/// Cubes are repeatedly combined with cubes that differ in a single variable,
+/// and the cubes that cannot be combined any further are the prime implicants.
+pub fn prime_implicants(minterms: &[u64], variables: usize) -> Vec<Cube> {
+    let mut cubes = minterms.iter()
+        .map(|minterm| Cube::minterm(*minterm, variables))
+        .collect::<BTreeSet<_>>();
+    let mut primes = BTreeSet::new();
+    while !cubes.is_empty() {
+        // Only cubes with the same mask, and one more true value, can be combined
+        let mut groups = BTreeMap::<(u64, u32), Vec<Cube>>::new();
+        for cube in &cubes {
+            groups.entry((cube.mask, cube.value.count_ones())).or_default().push(*cube);
+        }
+        let mut combined = BTreeSet::new();
+        let mut used = BTreeSet::new();
+        for ((mask, ones), group) in &groups {
+            let Some(next_group) = groups.get(&(*mask, ones + 1)) else {
+                continue;
+            };
+            for cube in group {
+                for other in next_group {
+                    if let Some(result) = cube.combine(other) {
+                        combined.insert(result);
+                        used.insert(*cube);
+                        used.insert(*other);
+                    }
+                }
+            }
+        }
+        primes.extend(cubes.difference(&used));
+        cubes = combined;
+    }
+    primes.into_iter().collect()
+}
+
+/// Selects the cheapest set of prime implicants that covers all the minterms.
+/// Essential prime implicants are always selected, and Petrick's method chooses among the rest.
+/// The cost of a cover is the number of implicants, and then the total number of literals.
+/// Returns `None` if Petrick's method would need more than [`MAX_PETRICK_PRODUCTS`] products.
+pub fn minimal_cover(primes: &[Cube], minterms: &[u64]) -> Option<Vec<Cube>> {
+    let mut cover = BTreeSet::new();
+    for minterm in minterms {
+        let mut covering = primes.iter().filter(|prime| prime.covers(*minterm));
+        if let (Some(essential), None) = (covering.next(), covering.next()) {
+            cover.insert(*essential);
+        }
+    }
+    let uncovered = minterms.iter()
+        .copied()
+        .filter(|minterm| !cover.iter().any(|cube| cube.covers(*minterm)))
+        .collect::<Vec<_>>();
+    cover.extend(petricks_method(primes, &uncovered)?);
+    Some(cover.into_iter().collect())
+}
+
+/// Multiplies out the product of sums, where each sum is the prime implicants covering one of the minterms,
+/// and returns the cheapest product, or `None` if there would be more than [`MAX_PETRICK_PRODUCTS`] products.
+/// Sums that contain another sum are removed first, `(P ⋁ Q) ⋀ (P ⋁ Q ⋁ R)` is `P ⋁ Q`,
+/// and products that contain another product are removed along the way, `P ⋁ P ⋀ Q` is `P`.
+fn petricks_method(primes: &[Cube], minterms: &[u64]) -> Option<Vec<Cube>> {
+    let sums = minimal_sets(minterms.iter()
+        .map(|minterm| (0..primes.len()).filter(|index| primes[*index].covers(*minterm)).collect())
+        .collect());
+    let mut products = vec![BTreeSet::<usize>::new()];
+    for sum in &sums {
+        let mut expanded = vec![];
+        for product in &products {
+            if !product.is_disjoint(sum) {
+                expanded.push(product.clone());
+                continue;
+            }
+            for index in sum {
+                let mut product = product.clone();
+                product.insert(*index);
+                expanded.push(product);
+            }
+        }
+        if expanded.len() > MAX_PETRICK_PRODUCTS {
+            return None;
+        }
+        products = minimal_sets(expanded);
+    }
+    let cheapest = products.into_iter()
+        .min_by_key(|product| (product.len(), product.iter().map(|index| primes[*index].literals()).sum::<u32>()))
+        .map(|product| product.into_iter().map(|index| primes[index]).collect())
+        .unwrap_or_default();
+    Some(cheapest)
+}
+
+/// Removes the duplicate sets and the sets that contain another set, and orders the rest from smallest to largest.
+fn minimal_sets(mut sets: Vec<BTreeSet<usize>>) -> Vec<BTreeSet<usize>> {
+    sets.sort_by(|left, right| left.len().cmp(&right.len()).then_with(|| left.cmp(right)));
+    sets.dedup();
+    let mut minimal = Vec::<BTreeSet<usize>>::new();
+    for set in sets {
+        if !minimal.iter().any(|other| other.is_subset(&set)) {
+            minimal.push(set);
+        }
+    }
+    minimal
+}
+
+/// Combines the cubes into a disjunction, or `⊥` if there are no cubes.
+pub fn sum_of_products(cubes: &[Cube], variables: &[String]) -> Expression {
+    let mut terms = cubes.iter()
+        .map(|cube| cube.to_expression(variables))
+        .collect::<Vec<_>>();
+    terms.sort_by(Expression::canonical_cmp);
+    chain(BinaryOperator::Or, terms).unwrap_or(Expression::Constant(false))
+}
+
+/// Assignments where the value of an expression does not matter, so a minimizer is free to choose it.
+/// The minterm indices use the atomic values of the expression ordered by name,
+/// and the expression may only use those atomic values.
+#[derive(Debug, Default, Clone)]
+pub struct DontCares {
+    pub minterms: Vec<u64>,
+    pub expression: Option<Expression>,
+}
+
+impl DontCares {
+    pub fn is_empty(&self) -> bool {
+        self.minterms.is_empty() && self.expression.is_none()
+    }
+
+    /// All don't-care minterms over the variables, in ascending order.
+    pub fn minterms(&self, variables: &[String]) -> Vec<u64> {
+        let mut minterms = self.minterms.clone();
+        if let Some(expression) = &self.expression {
+            minterms.extend(TruthTable::minterms(expression, variables));
+        }
+        minterms.sort();
+        minterms.dedup();
+        minterms
+    }
+}
+
+impl Expression {
+    /// Finds a minimal sum of products that is equivalent to the expression,
+    /// except in the `dont_cares`, using the Quine–McCluskey algorithm and Petrick's method.
+    /// The atomic values are ordered by name, and the whole truth table is evaluated,
+    /// so this is only feasible for expressions with few atomic values.
+    /// Returns an error if the expression has more than [`MAX_VARIABLES`] atomic values,
+    /// or Petrick's method would need more than [`MAX_PETRICK_PRODUCTS`] products.
+    pub fn minimal_sum_of_products(&self, dont_cares: &DontCares) -> Result<Self, LimitExceeded> {
+        let mut variables = self.get_atomic_values().into_iter().collect::<Vec<_>>();
+        if variables.len() > MAX_VARIABLES {
+            return Err(LimitExceeded::AtomicValues { limit: MAX_VARIABLES });
+        }
+        variables.sort();
+        let dont_care_minterms = dont_cares.minterms(&variables);
+        let minterms = TruthTable::minterms(self, &variables).into_iter()
+            .filter(|minterm| dont_care_minterms.binary_search(minterm).is_err())
+            .collect::<Vec<_>>();
+        if minterms.is_empty() {
+            return Ok(Expression::Constant(false));
+        }
+        let mut implicants = [minterms.as_slice(), dont_care_minterms.as_slice()].concat();
+        implicants.sort();
+        let primes = prime_implicants(&implicants, variables.len());
+        let cover = minimal_cover(&primes, &minterms)
+            .ok_or(LimitExceeded::Products { limit: MAX_PETRICK_PRODUCTS })?;
+        Ok(sum_of_products(&cover, &variables))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::expressions::helpers::{and, atomic, chain, constant, iff, implies, not, or, xor};
+    use crate::expressions::minimize::{Cube, DontCares, MAX_PETRICK_PRODUCTS, MAX_VARIABLES, minimal_cover, prime_implicants};
+    use crate::expressions::operator::BinaryOperator;
+    use crate::expressions::simplify::LimitExceeded;
+
+    fn cube(pattern: &str) -> Cube {
+        pattern.chars().fold(Cube { value: 0, mask: 0 }, |cube, c| Cube {
+            value: cube.value << 1 | (c == '1') as u64,
+            mask: cube.mask << 1 | (c != '-') as u64,
+        })
+    }
+
+    #[test]
+    fn test_cube_covers() {
+        assert!(cube("1-0").covers(0b100));
+        assert!(cube("1-0").covers(0b110));
+        assert!(!cube("1-0").covers(0b101));
+    }
+
+    #[test]
+    fn test_cube_contains() {
+        assert!(cube("1--").contains(&cube("1-0")));
+        assert!(!cube("1-0").contains(&cube("1--")));
+        assert!(!cube("1--").contains(&cube("0-0")));
+    }
+
+    #[test]
+    fn test_cube_intersect() {
+        assert_eq!(cube("1--").intersect(&cube("--0")), Some(cube("1-0")));
+        assert_eq!(cube("1--").intersect(&cube("0-0")), None);
+    }
+
+    #[test]
+    fn test_cube_supercube() {
+        assert_eq!(cube("110").supercube(&cube("100")), cube("1-0"));
+        assert_eq!(cube("11-").supercube(&cube("0-1")), cube("---"));
+    }
+
+    #[test]
+    fn test_cube_to_expression() {
+        let variables = ["a".to_string(), "b".to_string(), "c".to_string()];
+        assert_eq!(cube("1-0").to_expression(&variables), and(atomic("a"), not(atomic("c"))));
+        assert_eq!(cube("---").to_expression(&variables), constant(true));
+    }
+
+    #[test]
+    fn test_prime_implicants() {
+        // f(a, b, c, d) = Σm(4, 8, 10, 11, 12, 15)
+        let primes = prime_implicants(&[4, 8, 10, 11, 12, 15], 4);
+        assert_eq!(primes.len(), 5);
+        for prime in ["-100", "10-0", "1-00", "101-", "1-11"] {
+            assert!(primes.contains(&cube(prime)), "{prime} is not a prime implicant");
+        }
+    }
+
+    #[test]
+    fn test_minimal_cover_essential() {
+        let minterms = [4, 8, 10, 11, 12, 15];
+        let cover = minimal_cover(&prime_implicants(&minterms, 4), &minterms).unwrap();
+        assert_eq!(cover.len(), 3);
+        assert!(cover.contains(&cube("-100")));
+        assert!(cover.contains(&cube("1-11")));
+    }
+
+    #[test]
+    fn test_minimal_cover_cyclic() {
+        // Every minterm is covered by exactly two prime implicants, so none are essential
+        let minterms = [0, 1, 2, 5, 6, 7];
+        let primes = prime_implicants(&minterms, 3);
+        assert_eq!(primes.len(), 6);
+        assert_eq!(minimal_cover(&primes, &minterms).unwrap().len(), 3);
+    }
+
+    #[test]
+    fn test_minimal_sum_of_products() {
+        let expression = or(and(atomic("a"), atomic("b")), and(atomic("a"), not(atomic("b"))));
+        assert_eq!(expression.minimal_sum_of_products(&DontCares::default()).unwrap(), atomic("a"));
+    }
+
+    #[test]
+    fn test_minimal_sum_of_products_consensus() {
+        let expression = or(or(and(atomic("a"), atomic("b")), and(not(atomic("a")), atomic("c"))), and(atomic("b"), atomic("c")));
+        assert_eq!(expression.minimal_sum_of_products(&DontCares::default()).unwrap().to_string(), "a ⋀ b ⋁ ¬a ⋀ c");
+    }
+
+    #[test]
+    fn test_minimal_sum_of_products_implication() {
+        let expression = implies(atomic("a"), atomic("b"));
+        assert_eq!(expression.minimal_sum_of_products(&DontCares::default()).unwrap(), or(not(atomic("a")), atomic("b")));
+    }
+
+    #[test]
+    fn test_minimal_sum_of_products_xor() {
+        let expression = xor(atomic("a"), atomic("b"));
+        assert_eq!(expression.minimal_sum_of_products(&DontCares::default()).unwrap(), or(and(atomic("a"), not(atomic("b"))), and(not(atomic("a")), atomic("b"))));
+    }
+
+    #[test]
+    fn test_minimal_sum_of_products_dont_care_minterms() {
+        // f(a, c) = Σm(1) + d(3) = c
+        let expression = and(not(atomic("a")), atomic("c"));
+        let dont_cares = DontCares { minterms: vec![3], expression: None };
+        assert_eq!(expression.minimal_sum_of_products(&dont_cares).unwrap(), atomic("c"));
+    }
+
+    #[test]
+    fn test_minimal_sum_of_products_dont_care_expression() {
+        // The combination a ⋀ b never happens, so a ⋀ ¬b ⋁ ¬a ⋀ b can become a ⋁ b
+        let expression = xor(atomic("a"), atomic("b"));
+        let dont_cares = DontCares { minterms: vec![], expression: Some(and(atomic("a"), atomic("b"))) };
+        assert_eq!(expression.minimal_sum_of_products(&dont_cares).unwrap(), or(atomic("a"), atomic("b")));
+    }
+
+    #[test]
+    fn test_minimal_sum_of_products_only_dont_cares() {
+        let dont_cares = DontCares { minterms: vec![1], expression: None };
+        assert_eq!(atomic("a").minimal_sum_of_products(&dont_cares).unwrap(), constant(false));
+    }
+
+    #[test]
+    fn test_minimal_sum_of_products_constants() {
+        assert_eq!(or(atomic("a"), not(atomic("a"))).minimal_sum_of_products(&DontCares::default()).unwrap(), constant(true));
+        assert_eq!(iff(atomic("a"), not(atomic("a"))).minimal_sum_of_products(&DontCares::default()).unwrap(), constant(false));
+    }
+    #[test]
+    fn test_minimal_sum_of_products_max_variables() {
+        let variables = (0..MAX_VARIABLES).map(|index| format!("x{index:02}")).collect::<Vec<_>>();
+        let expression = or(
+            chain(BinaryOperator::And, variables.iter().map(atomic)).unwrap(),
+            chain(BinaryOperator::And, variables.iter().map(|variable| not(atomic(variable)))).unwrap(),
+        );
+        assert_eq!(expression.minimal_sum_of_products(&DontCares::default()).unwrap(), expression);
+    }
+
+    #[test]
+    fn test_minimal_sum_of_products_too_many_products() {
+        // Each factor is a cycle of prime implicants where none are essential, so the products multiply
+        let cycle = |a: &str, b: &str, c: &str| or(or(and(atomic(a), not(atomic(b))), and(atomic(b), not(atomic(c)))), and(atomic(c), not(atomic(a))));
+        let expression = and(and(cycle("a", "b", "c"), cycle("d", "e", "f")), cycle("g", "h", "i"));
+        assert_eq!(expression.minimal_sum_of_products(&DontCares::default()), Err(LimitExceeded::Products { limit: MAX_PETRICK_PRODUCTS }));
+    }
+}
@@ -2,4 +2,14 @@ pub mod expression;
 pub mod operator;
 pub mod helpers;
 pub mod simplify;
-pub mod truth_table;
\ No newline at end of file
+pub mod minimize;
+pub mod espresso;
+pub mod normal_form;
+pub mod sat;
+pub mod classify;
+pub mod bdd;
+pub mod syntax_tree;
+pub mod latex;
+pub mod notation;
+pub mod truth_table;
+pub mod karnaugh_map;
\ No newline at end of file
@@ -0,0 +1,483 @@
+use serde::Serialize;
+
+use crate::expressions::expression::Expression;
+use crate::expressions::helpers::binary;
+use crate::expressions::operator::BinaryOperator;
+use crate::expressions::simplify::Law;
+use crate::routing::response::Operation;
+
+/// The most literals a normal form may have, since distribution can make it grow exponentially.
+pub const MAX_NORMAL_FORM_SIZE: u64 = 1_000;
+
+/// An atomic value, or the negation of an atomic value, in a clause or term of a normal form.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize)]
+pub struct Literal {
+    pub name: String,
+    pub negated: bool,
+}
+
+impl Literal {
+    /// Creates a literal from `a` or `¬a`, returns `None` for any other expression.
+    pub fn from_expression(expression: &Expression) -> Option<Self> {
+        expression.as_literal().map(|(name, negated)| Self { name: name.into(), negated })
+    }
+}
+
+/// Upper bounds on the size of the normal forms of an expression, found without converting it.
+#[derive(Debug, Copy, Clone, PartialEq)]
+struct SizeBound {
+    /// The number of clauses in the conjunctive normal form.
+    clauses: u64,
+    /// The number of clauses in the conjunctive normal form of the negation,
+    /// which is also the number of terms in the disjunctive normal form.
+    negated_clauses: u64,
+    /// The number of literals in the negation normal form, which no clause or term can have more of.
+    literals: u64,
+}
+
+impl SizeBound {
+    const LITERAL: Self = Self { clauses: 1, negated_clauses: 1, literals: 1 };
+
+    const fn not(self) -> Self {
+        Self { clauses: self.negated_clauses, negated_clauses: self.clauses, literals: self.literals }
+    }
+
+    /// The clauses of both operands are kept, while every clause of the negations is combined with every other.
+    const fn and(self, other: Self) -> Self {
+        Self {
+            clauses: self.clauses.saturating_add(other.clauses),
+            negated_clauses: self.negated_clauses.saturating_mul(other.negated_clauses),
+            literals: self.literals.saturating_add(other.literals),
+        }
+    }
+
+    const fn or(self, other: Self) -> Self {
+        self.not().and(other.not()).not()
+    }
+}
+
+impl Expression {
+    /// An upper bound on the number of literals in the conjunctive normal form, found without converting the expression.
+    pub fn cnf_size_bound(&self) -> u64 {
+        let bound = self.size_bound();
+        bound.clauses.saturating_mul(bound.literals)
+    }
+
+    /// An upper bound on the number of literals in the disjunctive normal form, found without converting the expression.
+    pub fn dnf_size_bound(&self) -> u64 {
+        let bound = self.size_bound();
+        bound.negated_clauses.saturating_mul(bound.literals)
+    }
+
+    /// Bounds the normal forms of the expression after the operators are eliminated the same way as in [`Expression::to_nnf`].
+    fn size_bound(&self) -> SizeBound {
+        match self {
+            Expression::Not(expr) => expr.size_bound().not(),
+            Expression::Binary { left, operator, right } => {
+                let (left, right) = (left.size_bound(), right.size_bound());
+                match operator {
+                    BinaryOperator::And => left.and(right),
+                    BinaryOperator::Or => left.or(right),
+                    BinaryOperator::Implication => left.not().or(right),
+                    BinaryOperator::Biconditional => left.not().or(right).and(right.not().or(left)),
+                    BinaryOperator::Xor => left.and(right.not()).or(left.not().and(right)),
+                    BinaryOperator::Nand => left.and(right).not(),
+                    BinaryOperator::Nor => left.or(right).not(),
+                }
+            }
+            Expression::Atomic(_) | Expression::Constant(_) => SizeBound::LITERAL,
+        }
+    }
+
+    /// Convert the expression to conjunctive normal form, a conjunction of disjunctions of literals, `(a ⋁ b) ⋀ (¬a ⋁ c)`.
+    /// All operators except `⋀` and `⋁` are eliminated, negations are moved inwards to the atomic values,
+    /// and `⋁` is distributed over `⋀`.
+    /// The operations are returned in the order they were applied.
+    pub fn to_cnf(&self) -> (Self, Vec<Operation>) {
+        let mut operations = vec![];
+        let expression = self.negation_normal_form(&mut operations)
+            .remove_constants(&mut operations)
+            .distribute(BinaryOperator::Or, BinaryOperator::And, &mut operations)
+            .associative_law(&mut operations);
+        (expression, operations)
+    }
+
+    /// Convert the expression to disjunctive normal form, a disjunction of conjunctions of literals, `(a ⋀ b) ⋁ (¬a ⋀ c)`.
+    /// All operators except `⋀` and `⋁` are eliminated, negations are moved inwards to the atomic values,
+    /// and `⋀` is distributed over `⋁`.
+    /// The operations are returned in the order they were applied.
+    pub fn to_dnf(&self) -> (Self, Vec<Operation>) {
+        let mut operations = vec![];
+        let expression = self.negation_normal_form(&mut operations)
+            .remove_constants(&mut operations)
+            .distribute(BinaryOperator::And, BinaryOperator::Or, &mut operations)
+            .associative_law(&mut operations);
+        (expression, operations)
+    }
+
+    /// The clauses of an expression in conjunctive normal form, where each clause is a disjunction of its literals.
+    /// `⊤` has no clauses, and `⊥` has a single empty clause.
+    /// Returns `None` if the expression is not in conjunctive normal form.
+    pub fn clauses(&self) -> Option<Vec<Vec<Literal>>> {
+        self.normal_form_terms(BinaryOperator::And, BinaryOperator::Or)
+    }
+
+    /// The terms of an expression in disjunctive normal form, where each term is a conjunction of its literals.
+    /// `⊥` has no terms, and `⊤` has a single empty term.
+    /// Returns `None` if the expression is not in disjunctive normal form.
+    pub fn terms(&self) -> Option<Vec<Vec<Literal>>> {
+        self.normal_form_terms(BinaryOperator::Or, BinaryOperator::And)
+    }
+
+    fn normal_form_terms(&self, outer: BinaryOperator, inner: BinaryOperator) -> Option<Vec<Vec<Literal>>> {
+        match self {
+            Expression::Constant(value) if *value == (outer == BinaryOperator::And) => Some(vec![]),
+            Expression::Constant(_) => Some(vec![vec![]]),
+            expression => expression.operands(outer).into_iter()
+                .map(|term| term.operands(inner).into_iter().map(Literal::from_expression).collect())
+                .collect(),
+        }
+    }
+
+    /// Convert the expression to negation normal form, where `⋀` and `⋁` are the only operators,
+    /// and negations are only applied to atomic values and constants, `¬a ⋁ (b ⋀ ¬c)`.
+    /// All other operators are eliminated, and negations are moved inwards using De Morgan's laws,
+    /// until no double negations are left.
+    /// The operations are returned in the order they were applied.
+    pub fn to_nnf(&self) -> (Self, Vec<Operation>) {
+        let mut operations = vec![];
+        let expression = self.negation_normal_form(&mut operations);
+        (expression, operations)
+    }
+
+    fn negation_normal_form(&self, operations: &mut Vec<Operation>) -> Self {
+        let mut expression = self.elimination_of_exclusive_or(operations)
+            .elimination_of_nand(operations)
+            .elimination_of_nor(operations)
+            .elimination_of_biconditional(operations)
+            .elimination_of_implication(operations);
+        loop {
+            let result = expression.de_morgans_laws(operations)
+                .double_negation_elimination(operations);
+            if result == expression {
+                return result;
+            }
+            expression = result;
+        }
+    }
+
+    /// Remove all constants from an expression in negation normal form, unless the whole expression is a constant.
+    fn remove_constants(&self, operations: &mut Vec<Operation>) -> Self {
+        let mut expression = self.clone();
+        loop {
+            let result = expression.complement_law(operations, false)
+                .domination_law(operations)
+                .identity_law(operations);
+            if result == expression {
+                return result;
+            }
+            expression = result;
+        }
+    }
+
+    /// Distribute `operator` over `over`, until no `operator` has an operand using `over`.
+    /// For `⋁` over `⋀`, this is done by replacing `a ⋁ (b ⋀ c)` with `(a ⋁ b) ⋀ (a ⋁ c)`.
+    fn distribute(&self, operator: BinaryOperator, over: BinaryOperator, operations: &mut Vec<Operation>) -> Self {
+        let result = match self {
+            Expression::Binary { left, operator: op, right } if *op == operator => {
+                let left = left.distribute(operator, over, operations);
+                let right = right.distribute(operator, over, operations);
+                match (&left, &right) {
+                    (Expression::Binary { left: left_left, operator: op, right: left_right }, _) if *op == over => binary(
+                        binary(left_left.clone(), operator, right.clone()).distribute(operator, over, operations),
+                        over,
+                        binary(left_right.clone(), operator, right.clone()).distribute(operator, over, operations),
+                    ),
+                    (_, Expression::Binary { left: right_left, operator: op, right: right_right }) if *op == over => binary(
+                        binary(left.clone(), operator, right_left.clone()).distribute(operator, over, operations),
+                        over,
+                        binary(left.clone(), operator, right_right.clone()).distribute(operator, over, operations),
+                    ),
+                    _ => binary(left, operator, right),
+                }
+            }
+            Expression::Binary { left, operator: op, right } => binary(
+                left.distribute(operator, over, operations),
+                *op,
+                right.distribute(operator, over, operations),
+            ),
+            literal => literal.clone(),
+        };
+        if let Some(operation) = Operation::new(self, &result, Law::DistributionLaw) {
+            operations.push(operation);
+        }
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use crate::expressions::expression::Expression;
+    use crate::expressions::helpers::{and, atomic, constant, iff, implies, nand, nor, not, or, xor};
+    use crate::expressions::normal_form::Literal;
+    use crate::expressions::operator::BinaryOperator;
+    use crate::expressions::simplify::Law;
+
+    fn evaluate(expression: &Expression, values: &HashMap<String, bool>) -> bool {
+        match expression {
+            Expression::Not(expr) => !evaluate(expr, values),
+            Expression::Binary { left, operator, right } => operator.eval(evaluate(left, values), evaluate(right, values)),
+            Expression::Atomic(value) => values[value],
+            Expression::Constant(value) => *value,
+        }
+    }
+
+    fn assert_equivalent(left: &Expression, right: &Expression) {
+        let mut names = left.get_atomic_values();
+        names.extend(right.get_atomic_values());
+        let names = names.into_iter().collect::<Vec<_>>();
+        for row in 0..1 << names.len() {
+            let values = names.iter().enumerate()
+                .map(|(index, name)| (name.clone(), row >> index & 1 == 1))
+                .collect::<HashMap<_, _>>();
+            assert_eq!(evaluate(left, &values), evaluate(right, &values), "{left} and {right} differ for {values:?}");
+        }
+    }
+
+    fn is_nnf(expression: &Expression) -> bool {
+        match expression {
+            Expression::Not(expr) => matches!(expr.as_ref(), Expression::Atomic(_) | Expression::Constant(_)),
+            Expression::Binary { left, operator: BinaryOperator::And | BinaryOperator::Or, right } => is_nnf(left) && is_nnf(right),
+            Expression::Binary { .. } => false,
+            Expression::Atomic(_) | Expression::Constant(_) => true,
+        }
+    }
+
+    fn literal(name: &str, negated: bool) -> Literal {
+        Literal { name: name.into(), negated }
+    }
+
+    #[test]
+    fn test_to_nnf() {
+        let (expression, operations) = not(and(atomic("a"), not(or(atomic("b"), atomic("c"))))).to_nnf();
+        assert_eq!(expression, or(not(atomic("a")), or(atomic("b"), atomic("c"))));
+        assert!(operations.iter().any(|operation| operation.law == Law::DeMorgansLaws));
+        assert!(operations.iter().any(|operation| operation.law == Law::DoubleNegationElimination));
+    }
+
+    #[test]
+    fn test_to_nnf_double_negation_of_binary() {
+        let (expression, _) = not(not(and(atomic("a"), atomic("b")))).to_nnf();
+        assert_eq!(expression, and(atomic("a"), atomic("b")));
+    }
+
+    #[test]
+    fn test_to_nnf_implication() {
+        let (expression, operations) = not(implies(atomic("a"), atomic("b"))).to_nnf();
+        assert_eq!(expression, and(atomic("a"), not(atomic("b"))));
+        assert_eq!(operations[0].law, Law::EliminationOfImplication);
+    }
+
+    #[test]
+    fn test_to_nnf_keeps_distribution_and_constants() {
+        let expression = or(atomic("a"), and(not(atomic("b")), not(constant(true))));
+        let (nnf, operations) = expression.to_nnf();
+        assert_eq!(nnf, expression);
+        assert!(operations.is_empty());
+    }
+
+    #[test]
+    fn test_to_nnf_is_equivalent() {
+        let expressions = [
+            not(xor(atomic("a"), atomic("b"))),
+            not(nand(implies(atomic("a"), atomic("b")), iff(atomic("b"), not(atomic("c"))))),
+        ];
+        for expression in expressions {
+            let (nnf, _) = expression.to_nnf();
+            assert!(is_nnf(&nnf), "{nnf} is not in negation normal form");
+            assert_equivalent(&expression, &nnf);
+        }
+    }
+
+    #[test]
+    fn test_to_cnf_literal() {
+        let (expression, operations) = not(atomic("a")).to_cnf();
+        assert_eq!(expression, not(atomic("a")));
+        assert!(operations.is_empty());
+    }
+
+    #[test]
+    fn test_to_cnf_distribution() {
+        let (expression, operations) = or(atomic("a"), and(atomic("b"), atomic("c"))).to_cnf();
+        assert_eq!(expression, and(or(atomic("a"), atomic("b")), or(atomic("a"), atomic("c"))));
+        assert_eq!(operations.last().unwrap().law, Law::DistributionLaw);
+    }
+
+    #[test]
+    fn test_to_cnf_and_of_ands() {
+        let (expression, _) = or(and(atomic("a"), atomic("b")), and(atomic("c"), atomic("d"))).to_cnf();
+        assert_eq!(expression.to_string(), "(a ⋁ c) ⋀ (a ⋁ d) ⋀ (b ⋁ c) ⋀ (b ⋁ d)");
+    }
+
+    #[test]
+    fn test_to_cnf_implication() {
+        let (expression, operations) = implies(atomic("a"), and(atomic("b"), atomic("c"))).to_cnf();
+        assert_eq!(expression, and(or(not(atomic("a")), atomic("b")), or(not(atomic("a")), atomic("c"))));
+        assert_eq!(operations[0].law, Law::EliminationOfImplication);
+    }
+
+    #[test]
+    fn test_to_cnf_negated_conjunction() {
+        let (expression, _) = not(not(and(atomic("a"), not(or(atomic("b"), atomic("c")))))).to_cnf();
+        assert_eq!(expression, and(and(atomic("a"), not(atomic("b"))), not(atomic("c"))));
+    }
+
+    #[test]
+    fn test_to_cnf_is_equivalent() {
+        let expressions = [
+            iff(atomic("a"), atomic("b")),
+            xor(atomic("a"), or(atomic("b"), atomic("c"))),
+            nand(implies(atomic("a"), atomic("b")), iff(atomic("b"), not(atomic("c")))),
+            not(iff(xor(atomic("a"), atomic("b")), and(atomic("c"), atomic("d")))),
+        ];
+        for expression in expressions {
+            let (cnf, _) = expression.to_cnf();
+            assert!(cnf.clauses().is_some(), "{cnf} is not in conjunctive normal form");
+            assert_equivalent(&expression, &cnf);
+        }
+    }
+
+    #[test]
+    fn test_to_cnf_constants() {
+        assert_eq!(or(atomic("a"), constant(true)).to_cnf().0, constant(true));
+        assert_eq!(and(atomic("a"), not(constant(false))).to_cnf().0, atomic("a"));
+    }
+
+    #[test]
+    fn test_cnf_size_bound() {
+        // (a ⋁ b) ⋀ (a ⋁ c)
+        assert_eq!(or(atomic("a"), and(atomic("b"), atomic("c"))).cnf_size_bound(), 2 * 3);
+        // (a ⋀ ¬b) ⋁ (¬a ⋀ b) distributes to 4 clauses, none with more than its 4 literals
+        assert_eq!(xor(atomic("a"), atomic("b")).cnf_size_bound(), 4 * 4);
+    }
+
+    #[test]
+    fn test_cnf_size_bound_is_an_upper_bound() {
+        let expressions = [
+            or(and(atomic("a"), atomic("b")), and(not(atomic("c")), iff(atomic("d"), atomic("a")))),
+            nand(xor(atomic("a"), atomic("b")), implies(atomic("c"), or(atomic("d"), constant(false)))),
+        ];
+        for expression in expressions {
+            let literals = expression.to_cnf().0.clauses().unwrap().iter().map(Vec::len).sum::<usize>();
+            assert!(literals as u64 <= expression.cnf_size_bound());
+        }
+    }
+
+    #[test]
+    fn test_cnf_size_bound_grows_exponentially() {
+        let expression = (0..40).map(|index| and(atomic(format!("a{index}")), atomic(format!("b{index}"))))
+            .reduce(or)
+            .unwrap();
+        assert!(expression.cnf_size_bound() > super::MAX_NORMAL_FORM_SIZE);
+    }
+
+    #[test]
+    fn test_dnf_size_bound() {
+        // (a ⋀ b) ⋁ (a ⋀ c)
+        assert_eq!(and(atomic("a"), or(atomic("b"), atomic("c"))).dnf_size_bound(), 2 * 3);
+        assert_eq!(and(atomic("a"), atomic("b")).dnf_size_bound(), 2);
+    }
+
+    #[test]
+    fn test_dnf_size_bound_is_an_upper_bound() {
+        let expressions = [
+            and(or(atomic("a"), atomic("b")), or(not(atomic("c")), iff(atomic("d"), atomic("a")))),
+            nor(xor(atomic("a"), atomic("b")), implies(atomic("c"), and(atomic("d"), constant(true)))),
+        ];
+        for expression in expressions {
+            let literals = expression.to_dnf().0.terms().unwrap().iter().map(Vec::len).sum::<usize>();
+            assert!(literals as u64 <= expression.dnf_size_bound());
+        }
+    }
+
+    #[test]
+    fn test_dnf_size_bound_grows_exponentially() {
+        let expression = (0..40).map(|index| or(atomic(format!("a{index}")), atomic(format!("b{index}"))))
+            .reduce(and)
+            .unwrap();
+        assert!(expression.dnf_size_bound() > super::MAX_NORMAL_FORM_SIZE);
+    }
+
+    #[test]
+    fn test_to_dnf_distribution() {
+        let (expression, operations) = and(atomic("a"), or(atomic("b"), atomic("c"))).to_dnf();
+        assert_eq!(expression, or(and(atomic("a"), atomic("b")), and(atomic("a"), atomic("c"))));
+        assert_eq!(operations.last().unwrap().law, Law::DistributionLaw);
+    }
+
+    #[test]
+    fn test_to_dnf_or_of_ors() {
+        let (expression, _) = and(or(atomic("a"), atomic("b")), or(atomic("c"), atomic("d"))).to_dnf();
+        assert_eq!(expression.to_string(), "a ⋀ c ⋁ a ⋀ d ⋁ b ⋀ c ⋁ b ⋀ d");
+    }
+
+    #[test]
+    fn test_to_dnf_biconditional() {
+        let (expression, operations) = iff(atomic("a"), atomic("b")).to_dnf();
+        assert_eq!(expression.terms().map(|terms| terms.len()), Some(4));
+        assert_eq!(operations[0].law, Law::EliminationOfBiconditional);
+    }
+
+    #[test]
+    fn test_to_dnf_is_equivalent() {
+        let expressions = [
+            xor(atomic("a"), atomic("b")),
+            implies(or(atomic("a"), atomic("b")), and(atomic("c"), not(atomic("a")))),
+            nand(iff(atomic("a"), atomic("b")), or(atomic("b"), not(atomic("c")))),
+            not(iff(xor(atomic("a"), atomic("b")), and(atomic("c"), atomic("d")))),
+        ];
+        for expression in expressions {
+            let (dnf, _) = expression.to_dnf();
+            assert!(dnf.terms().is_some(), "{dnf} is not in disjunctive normal form");
+            assert_equivalent(&expression, &dnf);
+        }
+    }
+
+    #[test]
+    fn test_terms() {
+        let expression = or(and(atomic("a"), not(atomic("b"))), atomic("c"));
+        assert_eq!(expression.terms(), Some(vec![
+            vec![literal("a", false), literal("b", true)],
+            vec![literal("c", false)],
+        ]));
+    }
+
+    #[test]
+    fn test_terms_constants() {
+        assert_eq!(constant(false).terms(), Some(vec![]));
+        assert_eq!(constant(true).terms(), Some(vec![vec![]]));
+    }
+
+    #[test]
+    fn test_clauses() {
+        let expression = and(or(atomic("a"), not(atomic("b"))), atomic("c"));
+        assert_eq!(expression.clauses(), Some(vec![
+            vec![literal("a", false), literal("b", true)],
+            vec![literal("c", false)],
+        ]));
+    }
+
+    #[test]
+    fn test_clauses_constants() {
+        assert_eq!(constant(true).clauses(), Some(vec![]));
+        assert_eq!(constant(false).clauses(), Some(vec![vec![]]));
+    }
+
+    #[test]
+    fn test_clauses_not_cnf() {
+        assert_eq!(or(atomic("a"), and(atomic("b"), atomic("c"))).clauses(), None);
+        assert_eq!(not(and(atomic("a"), atomic("b"))).clauses(), None);
+    }
+}
@@ -0,0 +1,129 @@
+use serde::Deserialize;
+
+use crate::expressions::expression::{Expression, Symbols, UNICODE};
+
+/// The symbols expressions are written with in a response.
+/// Expressions written in any notation can be parsed again.
+#[derive(Debug, Default, Copy, Clone, PartialEq, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum Notation {
+    /// `¬a ⋀ b ⋁ c ➔ d`, the same as [`Display`](std::fmt::Display).
+    #[default]
+    Unicode,
+    /// `!a & b | c => d`
+    Ascii,
+    /// `!a && b || c -> d`, as in C and Rust.
+    C,
+    /// `not a and b or c implies d`
+    Python,
+    /// `NOT a AND b OR c IMPLIES d`
+    Word,
+}
+
+const ASCII: Symbols = Symbols {
+    not: "!",
+    and: "&",
+    or: "|",
+    implication: "=>",
+    biconditional: "<=>",
+    xor: "^",
+    nand: "nand",
+    nor: "nor",
+    truth: "1",
+    falsehood: "0",
+    atomic: str::to_string,
+};
+
+const C: Symbols = Symbols {
+    not: "!",
+    and: "&&",
+    or: "||",
+    implication: "->",
+    biconditional: "<->",
+    xor: "^",
+    nand: "nand",
+    nor: "nor",
+    truth: "true",
+    falsehood: "false",
+    atomic: str::to_string,
+};
+
+const PYTHON: Symbols = Symbols {
+    not: "not ",
+    and: "and",
+    or: "or",
+    implication: "implies",
+    biconditional: "iff",
+    xor: "xor",
+    nand: "nand",
+    nor: "nor",
+    truth: "True",
+    falsehood: "False",
+    atomic: str::to_string,
+};
+
+const WORD: Symbols = Symbols {
+    not: "NOT ",
+    and: "AND",
+    or: "OR",
+    implication: "IMPLIES",
+    biconditional: "IFF",
+    xor: "XOR",
+    nand: "NAND",
+    nor: "NOR",
+    truth: "TRUE",
+    falsehood: "FALSE",
+    atomic: str::to_string,
+};
+
+impl Notation {
+    fn symbols(&self) -> &'static Symbols {
+        match self {
+            Notation::Unicode => &UNICODE,
+            Notation::Ascii => &ASCII,
+            Notation::C => &C,
+            Notation::Python => &PYTHON,
+            Notation::Word => &WORD,
+        }
+    }
+}
+
+impl Expression {
+    /// Writes the expression in the given notation, using the same parentheses as [`Display`](std::fmt::Display).
+    pub fn to_string_in(&self, notation: Notation) -> String {
+        self.format(notation.symbols())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::expressions::expression::Expression;
+    use crate::expressions::helpers::{and, atomic, constant, iff, implies, not, or, xor};
+    use crate::expressions::notation::Notation;
+
+    fn expression() -> Expression {
+        implies(and(not(atomic("a")), atomic("b")), or(atomic("c"), not(and(atomic("d"), constant(true)))))
+    }
+
+    #[test]
+    fn test_unicode_is_display() {
+        assert_eq!(expression().to_string_in(Notation::Unicode), expression().to_string());
+    }
+
+    #[test]
+    fn test_to_string_in() {
+        assert_eq!(expression().to_string_in(Notation::Ascii), "!a & b => c | !(d & 1)");
+        assert_eq!(expression().to_string_in(Notation::C), "!a && b -> c || !(d && true)");
+        assert_eq!(expression().to_string_in(Notation::Python), "not a and b implies c or not (d and True)");
+        assert_eq!(expression().to_string_in(Notation::Word), "NOT a AND b IMPLIES c OR NOT (d AND TRUE)");
+    }
+
+    #[test]
+    fn test_parse_notation() {
+        let expression = iff(xor(atomic("a"), not(atomic("b"))), or(atomic("c"), constant(false)));
+        for notation in [Notation::Unicode, Notation::Ascii, Notation::C, Notation::Python, Notation::Word] {
+            let written = expression.to_string_in(notation);
+            assert_eq!(Expression::try_from(written.as_str()), Ok(expression.clone()), "{written}");
+        }
+    }
+}
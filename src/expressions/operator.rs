@@ -3,9 +3,13 @@ use serde::{Deserialize, Serialize};
 #[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Deserialize, Serialize)]
 #[serde(rename_all = "SCREAMING_SNAKE_CASE")]
 pub enum BinaryOperator {
+    Biconditional,
     Implication,
     Or,
+    Nor,
+    Xor,
     And,
+    Nand,
 }
 
 impl BinaryOperator {
@@ -14,6 +18,45 @@ impl BinaryOperator {
             BinaryOperator::And => left && right,
             BinaryOperator::Or => left || right,
             BinaryOperator::Implication => !left || right,
+            BinaryOperator::Biconditional => left == right,
+            BinaryOperator::Xor => left != right,
+            BinaryOperator::Nand => !(left && right),
+            BinaryOperator::Nor => !(left || right),
         }
     }
+
+    /// The symbol used when the operator is displayed.
+    pub const fn symbol(&self) -> &'static str {
+        match self {
+            BinaryOperator::And => "⋀",
+            BinaryOperator::Or => "⋁",
+            BinaryOperator::Implication => "➔",
+            BinaryOperator::Biconditional => "⇔",
+            BinaryOperator::Xor => "⊕",
+            BinaryOperator::Nand => "↑",
+            BinaryOperator::Nor => "↓",
+        }
+    }
+
+    /// How tightly the operator binds its operands when parsed, where a higher precedence binds more tightly.
+    /// Operators with the same precedence are left associative.
+    pub fn precedence(&self) -> u8 {
+        match self {
+            BinaryOperator::Biconditional => 0,
+            BinaryOperator::Implication => 1,
+            BinaryOperator::Or | BinaryOperator::Nor => 2,
+            BinaryOperator::Xor => 3,
+            BinaryOperator::And | BinaryOperator::Nand => 4,
+        }
+    }
+
+    /// Whether the order of the operands does not affect the result, `a ⋀ b == b ⋀ a`.
+    pub fn is_commutative(&self) -> bool {
+        !matches!(self, BinaryOperator::Implication)
+    }
+
+    /// Whether the grouping of a chain of the operator does not affect the result, `(a ⋀ b) ⋀ c == a ⋀ (b ⋀ c)`.
+    pub fn is_associative(&self) -> bool {
+        matches!(self, BinaryOperator::And | BinaryOperator::Or | BinaryOperator::Biconditional | BinaryOperator::Xor)
+    }
 }
@@ -0,0 +1,585 @@
+use std::collections::{BTreeMap, HashMap};
+
+use crate::expressions::expression::Expression;
+use crate::expressions::operator::BinaryOperator;
+
+/// Values for the atomic values of an expression, ordered by name.
+pub type Assignment = BTreeMap<String, bool>;
+
+impl Expression {
+    /// Finds values for the atomic values that make the expression true, or `None` if the expression is unsatisfiable.
+    /// Unlike a truth table, this does not try every combination, so it is feasible for expressions with many atomic values.
+    pub fn satisfying_assignment(&self) -> Option<Assignment> {
+        Solver::new(self).solve()
+    }
+
+    /// Iterates over every assignment that makes the expression true, finding one at a time with the solver.
+    pub fn models(&self) -> Models {
+        Models { solver: Solver::new(self) }
+    }
+
+    /// Counts the assignments that make the expression true, without enumerating them.
+    pub fn count_models(&self) -> Result<u128, CountLimitExceeded> {
+        let mut solver = Solver::new(self);
+        if solver.atomics.len() > MAX_COUNTED_ATOMICS {
+            return Err(CountLimitExceeded::AtomicValues);
+        }
+        solver.count().ok_or(CountLimitExceeded::Decisions)
+    }
+}
+
+/// The most atomic values an expression can have for its models to be counted, so the count fits in a [`u128`].
+pub const MAX_COUNTED_ATOMICS: usize = 127;
+
+/// The most decisions the solver can make while counting models,
+/// since expressions like a long chain of exclusive or have to be split on every atomic value.
+pub const MAX_COUNT_DECISIONS: usize = 100_000;
+
+/// The limit that was exceeded when the models of an expression could not be counted.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum CountLimitExceeded {
+    /// The expression has more than [`MAX_COUNTED_ATOMICS`] atomic values.
+    AtomicValues,
+    /// Counting needed more than [`MAX_COUNT_DECISIONS`] decisions.
+    Decisions,
+}
+
+/// The assignments that satisfy an expression, where every found assignment is excluded from the next search.
+#[derive(Debug)]
+pub struct Models {
+    solver: Solver,
+}
+
+impl Iterator for Models {
+    type Item = Assignment;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let assignment = self.solver.solve()?;
+        self.solver.block_assignment();
+        Some(assignment)
+    }
+}
+
+/// A variable, or its negation, encoded as `2 * variable + negated`.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+struct Literal(usize);
+
+impl Literal {
+    const fn new(variable: usize, negated: bool) -> Self {
+        Self(variable << 1 | negated as usize)
+    }
+
+    const fn variable(self) -> usize {
+        self.0 >> 1
+    }
+
+    const fn is_negated(self) -> bool {
+        self.0 & 1 == 1
+    }
+
+    const fn negate(self) -> Self {
+        Self(self.0 ^ 1)
+    }
+}
+
+/// A DPLL solver with unit propagation over two watched literals in every clause.
+/// The expression is converted into an equisatisfiable CNF with the Tseitin transformation,
+/// which introduces a variable for every operator, so the CNF grows linearly with the expression.
+#[derive(Debug)]
+pub struct Solver {
+    /// The atomic values of the expression, where the index is the variable.
+    atomics: Vec<String>,
+    /// Clauses with at least two literals, where the first two literals are watched.
+    clauses: Vec<Vec<Literal>>,
+    /// Clauses with a single literal, which are assigned before any decision.
+    units: Vec<Literal>,
+    /// The clauses watching each literal, indexed by the literal.
+    watches: Vec<Vec<usize>>,
+    /// The variables in the order they are decided, most frequently occurring first.
+    order: Vec<usize>,
+    values: Vec<Option<bool>>,
+    /// The assigned literals, in the order they were assigned.
+    trail: Vec<Literal>,
+    /// How many literals of the trail have been propagated.
+    propagated: usize,
+    decisions: Vec<Decision>,
+    /// Whether an empty clause was added.
+    empty_clause: bool,
+}
+
+/// A literal that was assigned without being implied by the clauses.
+#[derive(Debug)]
+struct Decision {
+    /// The length of the trail before the literal was assigned.
+    trail_length: usize,
+    literal: Literal,
+    /// Whether the opposite literal has already been tried.
+    flipped: bool,
+}
+
+impl Solver {
+    pub fn new(expression: &Expression) -> Self {
+        let mut atomics = expression.get_atomic_values().into_iter().collect::<Vec<_>>();
+        atomics.sort();
+        let mut encoder = Encoder {
+            variables: atomics.iter().enumerate().map(|(index, name)| (name.as_str(), index)).collect(),
+            variable_count: atomics.len(),
+            clauses: vec![],
+        };
+        let root = encoder.encode(expression);
+        encoder.clauses.push(vec![root]);
+        let Encoder { variable_count, clauses, .. } = encoder;
+
+        let mut occurrences = vec![0usize; variable_count];
+        for literal in clauses.iter().flatten() {
+            occurrences[literal.variable()] += 1;
+        }
+        let mut order = (0..variable_count).collect::<Vec<_>>();
+        order.sort_by_key(|variable| std::cmp::Reverse(occurrences[*variable]));
+
+        let mut solver = Self {
+            atomics,
+            clauses: vec![],
+            units: vec![],
+            watches: vec![vec![]; variable_count * 2],
+            order,
+            values: vec![None; variable_count],
+            trail: vec![],
+            propagated: 0,
+            decisions: vec![],
+            empty_clause: false,
+        };
+        for clause in clauses {
+            solver.add_clause(clause);
+        }
+        solver
+    }
+
+    /// Searches for an assignment that satisfies every clause, starting over on every call.
+    pub fn solve(&mut self) -> Option<Assignment> {
+        if !self.start() {
+            return None;
+        }
+        loop {
+            if !self.propagate() {
+                if !self.backtrack() {
+                    return None;
+                }
+                continue;
+            }
+            let Some(variable) = self.order.iter().copied().find(|variable| self.values[*variable].is_none()) else {
+                return Some(self.assignment());
+            };
+            let literal = Literal::new(variable, false);
+            self.decisions.push(Decision { trail_length: self.trail.len(), literal, flipped: false });
+            self.assign(literal);
+        }
+    }
+
+    /// Counts the assignments of the atomic values that satisfy every clause.
+    /// When every clause is satisfied before all atomic values are assigned,
+    /// each of the remaining atomic values can have any value, so those assignments are counted without visiting them.
+    /// Returns `None` if counting needs more than [`MAX_COUNT_DECISIONS`] decisions.
+    pub fn count(&mut self) -> Option<u128> {
+        if !self.start() {
+            return Some(0);
+        }
+        let mut remaining_decisions = MAX_COUNT_DECISIONS;
+        self.count_from_trail(&mut remaining_decisions)
+    }
+
+    fn count_from_trail(&mut self, remaining_decisions: &mut usize) -> Option<u128> {
+        if !self.propagate() {
+            return Some(0);
+        }
+        if self.clauses.iter().all(|clause| clause.iter().any(|literal| self.value(*literal) == Some(true))) {
+            let free = self.values[..self.atomics.len()].iter().filter(|value| value.is_none()).count();
+            return Some(1 << free);
+        }
+        let Some(variable) = self.order.iter().copied().find(|variable| self.values[*variable].is_none()) else {
+            return Some(0);
+        };
+        let trail_length = self.trail.len();
+        let mut count = 0;
+        for negated in [false, true] {
+            *remaining_decisions = remaining_decisions.checked_sub(1)?;
+            self.assign(Literal::new(variable, negated));
+            let branch = self.count_from_trail(remaining_decisions);
+            self.undo(trail_length);
+            count += branch?;
+        }
+        Some(count)
+    }
+
+    /// Excludes the current values of the atomic values from later solutions.
+    fn block_assignment(&mut self) {
+        let clause = (0..self.atomics.len())
+            .filter_map(|variable| self.values[variable].map(|value| Literal::new(variable, value)))
+            .collect();
+        self.add_clause(clause);
+    }
+
+    /// Clears the previous search and assigns the unit clauses.
+    /// Returns false if the clauses are already known to be unsatisfiable.
+    fn start(&mut self) -> bool {
+        self.values.fill(None);
+        self.trail.clear();
+        self.propagated = 0;
+        self.decisions.clear();
+        if self.empty_clause {
+            return false;
+        }
+        for index in 0..self.units.len() {
+            let unit = self.units[index];
+            match self.value(unit) {
+                Some(false) => return false,
+                Some(true) => {}
+                None => self.assign(unit),
+            }
+        }
+        true
+    }
+
+    /// Adds a clause that must be satisfied, removing duplicate literals and ignoring tautologies.
+    fn add_clause(&mut self, mut clause: Vec<Literal>) {
+        clause.sort();
+        clause.dedup();
+        if clause.windows(2).any(|pair| pair[0] == pair[1].negate()) {
+            return;
+        }
+        match clause.as_slice() {
+            [] => self.empty_clause = true,
+            [unit] => self.units.push(*unit),
+            [first, second, ..] => {
+                self.watches[first.0].push(self.clauses.len());
+                self.watches[second.0].push(self.clauses.len());
+                self.clauses.push(clause);
+            }
+        }
+    }
+
+    fn value(&self, literal: Literal) -> Option<bool> {
+        value(&self.values, literal)
+    }
+
+    fn assign(&mut self, literal: Literal) {
+        self.values[literal.variable()] = Some(!literal.is_negated());
+        self.trail.push(literal);
+    }
+
+    /// Assigns the literals implied by clauses with a single unassigned literal, until nothing changes.
+    /// Returns false if a clause has every literal false.
+    fn propagate(&mut self) -> bool {
+        while self.propagated < self.trail.len() {
+            let falsified = self.trail[self.propagated].negate();
+            self.propagated += 1;
+            let mut watching = std::mem::take(&mut self.watches[falsified.0]);
+            let mut index = 0;
+            let mut conflict = false;
+            while index < watching.len() {
+                let clause = &mut self.clauses[watching[index]];
+                if clause[0] == falsified {
+                    clause.swap(0, 1);
+                }
+                let other = clause[0];
+                if value(&self.values, other) == Some(true) {
+                    index += 1;
+                    continue;
+                }
+                if let Some(replacement) = (2..clause.len()).find(|i| value(&self.values, clause[*i]) != Some(false)) {
+                    clause.swap(1, replacement);
+                    self.watches[clause[1].0].push(watching[index]);
+                    watching.swap_remove(index);
+                    continue;
+                }
+                if value(&self.values, other).is_none() {
+                    self.assign(other);
+                    index += 1;
+                } else {
+                    conflict = true;
+                    break;
+                }
+            }
+            self.watches[falsified.0] = watching;
+            if conflict {
+                return false;
+            }
+        }
+        true
+    }
+
+    /// Undoes the assignments up to the last decision that has not been flipped, and flips it.
+    /// Returns false if every decision has been flipped, so the clauses are unsatisfiable.
+    fn backtrack(&mut self) -> bool {
+        while let Some(decision) = self.decisions.pop() {
+            self.undo(decision.trail_length);
+            if !decision.flipped {
+                let literal = decision.literal.negate();
+                self.decisions.push(Decision { trail_length: decision.trail_length, literal, flipped: true });
+                self.assign(literal);
+                return true;
+            }
+        }
+        false
+    }
+
+    /// Unassigns the literals after the first `trail_length` literals of the trail.
+    fn undo(&mut self, trail_length: usize) {
+        for literal in self.trail.drain(trail_length..) {
+            self.values[literal.variable()] = None;
+        }
+        self.propagated = trail_length;
+    }
+
+    fn assignment(&self) -> Assignment {
+        self.atomics.iter()
+            .enumerate()
+            .map(|(variable, name)| (name.clone(), self.values[variable].unwrap_or_default()))
+            .collect()
+    }
+}
+
+fn value(values: &[Option<bool>], literal: Literal) -> Option<bool> {
+    values[literal.variable()].map(|value| value != literal.is_negated())
+}
+
+/// Converts an expression into clauses with the Tseitin transformation.
+struct Encoder<'a> {
+    variables: HashMap<&'a str, usize>,
+    variable_count: usize,
+    clauses: Vec<Vec<Literal>>,
+}
+
+impl<'a> Encoder<'a> {
+    /// Returns a literal that is true exactly when the expression is true, given the clauses added along the way.
+    fn encode(&mut self, expression: &'a Expression) -> Literal {
+        match expression {
+            Expression::Atomic(name) => Literal::new(self.variables[name.as_str()], false),
+            Expression::Not(expression) => self.encode(expression).negate(),
+            Expression::Constant(value) => {
+                let literal = self.fresh();
+                self.clauses.push(vec![literal]);
+                if *value { literal } else { literal.negate() }
+            }
+            Expression::Binary { left, operator, right } => {
+                let left = self.encode(left);
+                let right = self.encode(right);
+                match operator {
+                    BinaryOperator::And => self.and(left, right),
+                    BinaryOperator::Nand => self.and(left, right).negate(),
+                    BinaryOperator::Or => self.and(left.negate(), right.negate()).negate(),
+                    BinaryOperator::Nor => self.and(left.negate(), right.negate()),
+                    BinaryOperator::Implication => self.and(left, right.negate()).negate(),
+                    BinaryOperator::Biconditional => self.biconditional(left, right),
+                    BinaryOperator::Xor => self.biconditional(left, right).negate(),
+                }
+            }
+        }
+    }
+
+    fn fresh(&mut self) -> Literal {
+        self.variable_count += 1;
+        Literal::new(self.variable_count - 1, false)
+    }
+
+    /// `x ⇔ a ⋀ b`
+    fn and(&mut self, left: Literal, right: Literal) -> Literal {
+        let gate = self.fresh();
+        self.clauses.push(vec![gate.negate(), left]);
+        self.clauses.push(vec![gate.negate(), right]);
+        self.clauses.push(vec![gate, left.negate(), right.negate()]);
+        gate
+    }
+
+    /// `x ⇔ (a ⇔ b)`
+    fn biconditional(&mut self, left: Literal, right: Literal) -> Literal {
+        let gate = self.fresh();
+        self.clauses.push(vec![gate.negate(), left.negate(), right]);
+        self.clauses.push(vec![gate.negate(), left, right.negate()]);
+        self.clauses.push(vec![gate, left, right]);
+        self.clauses.push(vec![gate, left.negate(), right.negate()]);
+        gate
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::expressions::expression::Expression;
+    use crate::expressions::helpers::{and, atomic, chain, constant, iff, implies, nand, nor, not, or, xor};
+    use crate::expressions::operator::BinaryOperator;
+    use crate::expressions::sat::{Assignment, CountLimitExceeded};
+
+    fn evaluate(expression: &Expression, assignment: &Assignment) -> bool {
+        match expression {
+            Expression::Not(expression) => !evaluate(expression, assignment),
+            Expression::Binary { left, operator, right } => {
+                operator.eval(evaluate(left, assignment), evaluate(right, assignment))
+            }
+            Expression::Atomic(name) => assignment[name],
+            Expression::Constant(value) => *value,
+        }
+    }
+
+    fn assert_satisfiable(expression: &Expression) -> Assignment {
+        let assignment = expression.satisfying_assignment().expect("expression is satisfiable");
+        assert_eq!(assignment.len(), expression.get_atomic_values().len());
+        assert!(evaluate(expression, &assignment), "{assignment:?} does not satisfy {expression}");
+        assignment
+    }
+
+    #[test]
+    fn test_satisfiable() {
+        let assignment = assert_satisfiable(&and(atomic("a"), not(atomic("b"))));
+        assert_eq!(assignment, Assignment::from([("a".to_string(), true), ("b".to_string(), false)]));
+    }
+
+    #[test]
+    fn test_unsatisfiable() {
+        assert!(and(atomic("a"), not(atomic("a"))).satisfying_assignment().is_none());
+        let expression = and(xor(atomic("a"), atomic("b")), iff(atomic("a"), atomic("b")));
+        assert!(expression.satisfying_assignment().is_none());
+    }
+
+    #[test]
+    fn test_constants() {
+        assert_eq!(constant(true).satisfying_assignment(), Some(Assignment::new()));
+        assert!(constant(false).satisfying_assignment().is_none());
+        assert_satisfiable(&or(constant(false), atomic("a")));
+        assert!(and(constant(false), atomic("a")).satisfying_assignment().is_none());
+    }
+
+    #[test]
+    fn test_every_operator() {
+        let (a, b) = (atomic("a"), atomic("b"));
+        for expression in [
+            implies(a.clone(), b.clone()),
+            nand(a.clone(), b.clone()),
+            nor(a.clone(), b.clone()),
+            xor(a.clone(), b.clone()),
+            iff(a.clone(), not(b.clone())),
+            and(not(implies(a.clone(), b.clone())), nand(a.clone(), b.clone())),
+        ] {
+            assert_satisfiable(&expression);
+        }
+        assert!(and(nor(a.clone(), b.clone()), or(a, b)).satisfying_assignment().is_none());
+    }
+
+    #[test]
+    fn test_many_variables() {
+        // x0 ⊕ x1 ⊕ … ⊕ x59 ⋀ x0 ⋀ … ⋀ x58, only satisfiable if x59 is false
+        let variables = (0..60).map(|index| atomic(format!("x{index:02}"))).collect::<Vec<_>>();
+        let parity = chain(BinaryOperator::Xor, variables.clone()).unwrap();
+        let prefix = chain(BinaryOperator::And, variables[..59].to_vec()).unwrap();
+        let assignment = assert_satisfiable(&and(parity, prefix));
+        assert!(!assignment["x59"]);
+    }
+
+    #[test]
+    fn test_pigeonhole_is_unsatisfiable() {
+        // 5 pigeons in 4 holes, every pigeon in a hole and no hole with two pigeons
+        let pigeon = |p: usize, h: usize| atomic(format!("p{p}h{h}"));
+        let mut constraints = (0..5)
+            .map(|p| chain(BinaryOperator::Or, (0..4).map(|h| pigeon(p, h))).unwrap())
+            .collect::<Vec<_>>();
+        for h in 0..4 {
+            for p in 0..5 {
+                for q in p + 1..5 {
+                    constraints.push(nand(pigeon(p, h), pigeon(q, h)));
+                }
+            }
+        }
+        let expression = chain(BinaryOperator::And, constraints).unwrap();
+        assert!(expression.satisfying_assignment().is_none());
+    }
+
+    #[test]
+    fn test_matches_truth_table() {
+        let (a, b, c) = (atomic("a"), atomic("b"), atomic("c"));
+        let expressions = [
+            or(and(a.clone(), b.clone()), and(not(a.clone()), c.clone())),
+            and(iff(a.clone(), b.clone()), xor(b.clone(), c.clone())),
+            and(and(implies(a.clone(), b.clone()), implies(b.clone(), c.clone())), and(a.clone(), not(c.clone()))),
+        ];
+        for expression in expressions {
+            let atomics = ["a", "b", "c"];
+            let satisfiable = (0..8).any(|index: u32| {
+                let assignment = atomics.iter()
+                    .enumerate()
+                    .map(|(bit, name)| (name.to_string(), index >> bit & 1 == 1))
+                    .collect();
+                evaluate(&expression, &assignment)
+            });
+            assert_eq!(expression.satisfying_assignment().is_some(), satisfiable, "{expression}");
+        }
+    }
+
+    #[test]
+    fn test_models() {
+        let expression = or(atomic("a"), atomic("b"));
+        let mut models = expression.models().collect::<Vec<_>>();
+        models.sort();
+        assert_eq!(models, vec![
+            Assignment::from([("a".to_string(), false), ("b".to_string(), true)]),
+            Assignment::from([("a".to_string(), true), ("b".to_string(), false)]),
+            Assignment::from([("a".to_string(), true), ("b".to_string(), true)]),
+        ]);
+        assert!(models.iter().all(|model| evaluate(&expression, model)));
+    }
+
+    #[test]
+    fn test_models_constants() {
+        assert_eq!(constant(true).models().collect::<Vec<_>>(), vec![Assignment::new()]);
+        assert_eq!(constant(false).models().count(), 0);
+        assert_eq!(and(atomic("a"), not(atomic("a"))).models().count(), 0);
+    }
+
+    #[test]
+    fn test_models_are_lazy() {
+        let variables = (0..40).map(|index| atomic(format!("x{index:02}")));
+        let expression = chain(BinaryOperator::Or, variables).unwrap();
+        assert_eq!(expression.models().take(5).count(), 5);
+    }
+
+    #[test]
+    fn test_count_models() {
+        assert_eq!(or(atomic("a"), atomic("b")).count_models(), Ok(3));
+        assert_eq!(xor(atomic("a"), atomic("b")).count_models(), Ok(2));
+        assert_eq!(and(atomic("a"), not(atomic("a"))).count_models(), Ok(0));
+        assert_eq!(constant(true).count_models(), Ok(1));
+        assert_eq!(constant(false).count_models(), Ok(0));
+    }
+
+    #[test]
+    fn test_count_models_matches_enumeration() {
+        let (a, b, c, d) = (atomic("a"), atomic("b"), atomic("c"), atomic("d"));
+        let expressions = [
+            or(and(a.clone(), b.clone()), and(not(a.clone()), c.clone())),
+            and(iff(a.clone(), b.clone()), xor(c.clone(), d.clone())),
+            implies(and(a.clone(), b.clone()), or(c.clone(), nand(d.clone(), a.clone()))),
+            nor(and(a, b), or(c, d)),
+        ];
+        for expression in expressions {
+            assert_eq!(expression.count_models(), Ok(expression.models().count() as u128), "{expression}");
+        }
+    }
+
+    #[test]
+    fn test_count_models_many_variables() {
+        let variables = (0..100).map(|index| atomic(format!("x{index:02}")));
+        let expression = chain(BinaryOperator::Or, variables).unwrap();
+        assert_eq!(expression.count_models(), Ok((1 << 100) - 1));
+    }
+
+    #[test]
+    fn test_count_models_too_many_variables() {
+        let variables = (0..128).map(|index| atomic(format!("x{index:03}")));
+        assert_eq!(chain(BinaryOperator::Or, variables).unwrap().count_models(), Err(CountLimitExceeded::AtomicValues));
+    }
+
+    #[test]
+    fn test_count_models_too_many_decisions() {
+        let variables = (0..40).map(|index| atomic(format!("x{index:02}")));
+        let expression = chain(BinaryOperator::Xor, variables).unwrap();
+        assert_eq!(expression.count_models(), Err(CountLimitExceeded::Decisions));
+    }
+}
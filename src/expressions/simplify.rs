@@ -1,16 +1,22 @@
 use std::ops::Deref;
 
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 
 use crate::expressions::expression::Expression;
-use crate::expressions::helpers::{and, binary, not, or};
+use crate::expressions::helpers::{and, binary, chain, implies, not, or};
+use crate::expressions::minimize::DontCares;
 use crate::expressions::operator::BinaryOperator;
 use crate::routing::options::SimplifyOptions;
 use crate::routing::response::Operation;
 
-#[derive(Debug, PartialEq, Serialize)]
+#[derive(Debug, Copy, Clone, PartialEq, Serialize)]
 #[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+#[allow(clippy::enum_variant_names)]
 pub enum Law {
+    EliminationOfExclusiveOr,
+    EliminationOfNand,
+    EliminationOfNor,
+    EliminationOfBiconditional,
     EliminationOfImplication,
     DeMorgansLaws,
     AbsorptionLaw,
@@ -18,6 +24,31 @@ pub enum Law {
     DistributionLaw,
     DoubleNegationElimination,
     CommutativeLaw,
+    IdentityLaw,
+    DominationLaw,
+    ComplementLaw,
+}
+
+impl Law {
+    /// The name of the law, as written next to a step of a derivation.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Law::EliminationOfExclusiveOr => "Elimination of exclusive or",
+            Law::EliminationOfNand => "Elimination of NAND",
+            Law::EliminationOfNor => "Elimination of NOR",
+            Law::EliminationOfBiconditional => "Elimination of biconditional",
+            Law::EliminationOfImplication => "Elimination of implication",
+            Law::DeMorgansLaws => "De Morgan's laws",
+            Law::AbsorptionLaw => "Absorption law",
+            Law::AssociativeLaw => "Associative law",
+            Law::DistributionLaw => "Distribution law",
+            Law::DoubleNegationElimination => "Double negation elimination",
+            Law::CommutativeLaw => "Commutative law",
+            Law::IdentityLaw => "Identity law",
+            Law::DominationLaw => "Domination law",
+            Law::ComplementLaw => "Complement law",
+        }
+    }
 }
 
 #[macro_export]
@@ -58,34 +89,171 @@ macro_rules! distribution_law_atomic_vs_binary {
     };
 }
 
-#[derive(Debug, Default)]
+/// The default number of times the laws are applied, if the expression keeps changing.
+pub const DEFAULT_MAX_ITERATIONS: usize = 10;
+
+/// How an expression is simplified.
+#[derive(Debug, Default, Copy, Clone, PartialEq, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum Strategy {
+    /// Apply the laws in [`Law`], and record every step.
+    #[default]
+    Laws,
+    /// Find a minimal sum of products, see [`Expression::minimal_sum_of_products`].
+    MinimalSop,
+    /// Find a small sum of products for expressions with many atomic values, see [`Expression::espresso`].
+    Espresso,
+}
+
+/// The expression is too large for the [`Strategy`] to simplify.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum LimitExceeded {
+    /// The expression has more atomic values than the strategy supports, where `limit` is the most it supports.
+    AtomicValues { limit: usize },
+    /// A cover built by [`Expression::espresso`] would have more than `limit` cubes.
+    Cubes { limit: usize },
+    /// Petrick's method in [`Expression::minimal_sum_of_products`] would have more than `limit` products.
+    Products { limit: usize },
+}
+
+#[derive(Debug)]
 pub struct Options {
     pub ignore_case: bool,
+    /// The maximum number of times the laws are applied, before the simplification stops.
+    pub max_iterations: usize,
+    pub strategy: Strategy,
+    /// The assignments where the result may have any value, ignored by [`Strategy::Laws`].
+    pub dont_cares: DontCares,
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Self {
+            ignore_case: false,
+            max_iterations: DEFAULT_MAX_ITERATIONS,
+            strategy: Strategy::default(),
+            dont_cares: DontCares::default(),
+        }
+    }
 }
 
 impl From<SimplifyOptions> for Options {
     fn from(options: SimplifyOptions) -> Self {
-        Self { ignore_case: options.ignore_case }
+        Self {
+            ignore_case: options.ignore_case,
+            max_iterations: options.max_iterations,
+            strategy: options.strategy,
+            dont_cares: DontCares::default(),
+        }
     }
 }
 
 impl Expression {
-    // TODO better track of operations
-    pub fn simplify(&self, options: Options) -> (Self, Vec<Operation>) {
+    /// Simplify the expression by applying the laws repeatedly, until the expression stops changing,
+    /// or `options.max_iterations` is reached.
+    /// The operations of every iteration are returned in the order they were applied.
+    /// If the strategy is not [`Strategy::Laws`], a sum of products is returned without any operations,
+    /// or an error if the expression is too large for the strategy.
+    pub fn simplify(&self, options: Options) -> Result<(Self, Vec<Operation>), LimitExceeded> {
+        match options.strategy {
+            Strategy::Laws => {}
+            Strategy::MinimalSop => {
+                return Ok((self.minimal_sum_of_products(&options.dont_cares)?, vec![]));
+            }
+            Strategy::Espresso => {
+                return Ok((self.espresso(&options.dont_cares)?, vec![]));
+            }
+        }
         let mut operations: Vec<Operation> = vec![];
-        let expression = self.elimination_of_implication(&mut operations)
-            .de_morgans_laws(&mut operations)
-            .absorption_law(&mut operations, options.ignore_case)
-            // .associative_law(&mut operations)
-            .distribution_law(&mut operations)
-            .double_negation_elimination(&mut operations);
-        // .commutative_law(&mut operations);
-        (expression, operations)
+        let mut expression = self.clone();
+        for _ in 0..options.max_iterations {
+            let simplified = expression.apply_laws(&mut operations, options.ignore_case);
+            if simplified == expression {
+                break;
+            }
+            expression = simplified;
+        }
+        Ok((expression, operations))
+    }
+
+    // TODO better track of operations
+    fn apply_laws(&self, operations: &mut Vec<Operation>, ignore_case: bool) -> Self {
+        self.elimination_of_exclusive_or(operations)
+            .elimination_of_nand(operations)
+            .elimination_of_nor(operations)
+            .elimination_of_biconditional(operations)
+            .elimination_of_implication(operations)
+            .de_morgans_laws(operations)
+            .associative_law(operations)
+            .complement_law(operations, ignore_case)
+            .domination_law(operations)
+            .identity_law(operations)
+            .absorption_law(operations, ignore_case)
+            .distribution_law(operations)
+            .double_negation_elimination(operations)
+            .commutative_law(operations)
+    }
+
+    /// Eliminate the exclusive or operator from the expression.
+    /// This is done by replacing `a ⊕ b` with `a ⋀ ¬b ⋁ ¬a ⋀ b`.
+    pub(super) fn elimination_of_exclusive_or(&self, operations: &mut Vec<Operation>) -> Self {
+        self.eliminate_operator(BinaryOperator::Xor, Law::EliminationOfExclusiveOr, operations, |left, right| {
+            or(and(left.clone(), not(right.clone())), and(not(left), right))
+        })
+    }
+
+    /// Eliminate the NAND operator from the expression.
+    /// This is done by replacing `a ↑ b` with `¬(a ⋀ b)`.
+    pub(super) fn elimination_of_nand(&self, operations: &mut Vec<Operation>) -> Self {
+        self.eliminate_operator(BinaryOperator::Nand, Law::EliminationOfNand, operations, |left, right| {
+            not(and(left, right))
+        })
+    }
+
+    /// Eliminate the NOR operator from the expression.
+    /// This is done by replacing `a ↓ b` with `¬(a ⋁ b)`.
+    pub(super) fn elimination_of_nor(&self, operations: &mut Vec<Operation>) -> Self {
+        self.eliminate_operator(BinaryOperator::Nor, Law::EliminationOfNor, operations, |left, right| {
+            not(or(left, right))
+        })
+    }
+
+    /// Eliminate the biconditional operator from the expression.
+    /// This is done by replacing `a ⇔ b` with `(a ➔ b) ⋀ (b ➔ a)`.
+    pub(super) fn elimination_of_biconditional(&self, operations: &mut Vec<Operation>) -> Self {
+        self.eliminate_operator(BinaryOperator::Biconditional, Law::EliminationOfBiconditional, operations, |left, right| {
+            and(implies(left.clone(), right.clone()), implies(right, left))
+        })
+    }
+
+    /// Replace every occurrence of the `target` operator, with the expression created by `rewrite` from its operands.
+    fn eliminate_operator<F>(&self, target: BinaryOperator, law: Law, operations: &mut Vec<Operation>, rewrite: F) -> Self
+    where
+        F: Fn(Expression, Expression) -> Expression + Copy,
+    {
+        let result = match self {
+            Expression::Not(expr) => not(expr.eliminate_operator(target, law, operations, rewrite)),
+            Expression::Binary { left, operator, right } => {
+                let l_result = left.eliminate_operator(target, law, operations, rewrite);
+                let r_result = right.eliminate_operator(target, law, operations, rewrite);
+
+                if *operator == target {
+                    rewrite(l_result, r_result)
+                } else {
+                    binary(l_result, *operator, r_result)
+                }
+            }
+            atomic @ (Expression::Atomic(_) | Expression::Constant(_)) => atomic.clone(),
+        };
+        if let Some(operation) = Operation::new(self, &result, law) {
+            operations.push(operation);
+        }
+        result
     }
 
     /// Eliminate the implication operator from the expression.
     /// This is done by replacing `a ➔ b` with `¬a ⋁ b`.
-    fn elimination_of_implication(&self, operations: &mut Vec<Operation>) -> Self {
+    pub(super) fn elimination_of_implication(&self, operations: &mut Vec<Operation>) -> Self {
         let result = match self {
             Expression::Not(expr) => not(expr.elimination_of_implication(operations)),
             Expression::Binary { left, operator, right } => {
@@ -98,7 +266,7 @@ impl Expression {
                     binary(l_result, *operator, r_result)
                 }
             }
-            atomic @ Expression::Atomic(_) => atomic.clone(),
+            atomic @ (Expression::Atomic(_) | Expression::Constant(_)) => atomic.clone(),
         };
         if let Some(operation) = Operation::new(self, &result, Law::EliminationOfImplication) {
             operations.push(operation);
@@ -109,7 +277,7 @@ impl Expression {
     /// Eliminate double negations from the expression.
     /// This is done by replacing `¬¬a` with `a`.
     /// This function is recursive and will continue to eliminate double negations until none are left.
-    fn double_negation_elimination(&self, operations: &mut Vec<Operation>) -> Self {
+    pub(super) fn double_negation_elimination(&self, operations: &mut Vec<Operation>) -> Self {
         let result = match self {
             Expression::Not(expr) => {
                 if let Expression::Not(inner) = expr.deref() {
@@ -123,7 +291,7 @@ impl Expression {
                 let right = right.double_negation_elimination(operations);
                 binary(left.clone(), *operator, right.clone())
             }
-            atomic @ Expression::Atomic(_) => atomic.clone(),
+            atomic @ (Expression::Atomic(_) | Expression::Constant(_)) => atomic.clone(),
         };
         if let Some(operation) = Operation::new(self, &result, Law::DoubleNegationElimination) {
             operations.push(operation);
@@ -131,7 +299,7 @@ impl Expression {
         result
     }
 
-    fn de_morgans_laws(&self, operations: &mut Vec<Operation>) -> Self {
+    pub(super) fn de_morgans_laws(&self, operations: &mut Vec<Operation>) -> Self {
         let result = match self {
             Expression::Not(expr) => {
                 match expr.deref() {
@@ -152,7 +320,7 @@ impl Expression {
                 let right = right.de_morgans_laws(operations);
                 binary(left, *operator, right)
             }
-            atomic @ Expression::Atomic(_) => atomic.clone(),
+            atomic @ (Expression::Atomic(_) | Expression::Constant(_)) => atomic.clone(),
         };
         if let Some(operation) = Operation::new(self, &result, Law::DeMorgansLaws) {
             operations.push(operation);
@@ -166,6 +334,10 @@ impl Expression {
             if Expression::eq(left, right, ignore_case) => {
                 left.absorption_law(operations, ignore_case)
             }
+            Expression::Binary { operator: operator @ (BinaryOperator::And | BinaryOperator::Or), .. }
+            if self.has_duplicate_operands(*operator, ignore_case) => {
+                self.remove_duplicate_operands(*operator, ignore_case).absorption_law(operations, ignore_case)
+            }
             Expression::Binary { left, operator: BinaryOperator::And, right } => {
                 absorption_law_opposites!(left, right, operations, BinaryOperator::Or, and, ignore_case)
             }
@@ -186,8 +358,125 @@ impl Expression {
         result
     }
 
-    fn associative_law(&self, operations: &mut Vec<Operation>) -> Self {
-        todo!("? | Associative law: (a ⋀ b) ⋀ c == a ⋀ (b ⋀ c) and (a ⋁ b) ⋁ c == a ⋁ (b ⋁ c)")
+    fn has_duplicate_operands(&self, operator: BinaryOperator, ignore_case: bool) -> bool {
+        let operands = self.operands(operator);
+        operands.iter().enumerate()
+            .any(|(index, operand)| operands[..index].iter().any(|other| Expression::eq(operand, other, ignore_case)))
+    }
+
+    /// Removes operands that occur more than once in a chain of the same operator.
+    /// This is done by replacing `a ⋀ b ⋀ a` with `a ⋀ b`, keeping the first occurrence.
+    fn remove_duplicate_operands(&self, operator: BinaryOperator, ignore_case: bool) -> Self {
+        let mut distinct: Vec<&Expression> = vec![];
+        for operand in self.operands(operator) {
+            if !distinct.iter().any(|other| Expression::eq(operand, other, ignore_case)) {
+                distinct.push(operand);
+            }
+        }
+        chain(operator, distinct.into_iter().cloned()).unwrap_or_else(|| self.clone())
+    }
+
+    /// Regroup chains of the same operator, so they are always left associated.
+    /// This is done by replacing `a ⋀ (b ⋀ c)` with `(a ⋀ b) ⋀ c` and `a ⋁ (b ⋁ c)` with `(a ⋁ b) ⋁ c`.
+    pub(super) fn associative_law(&self, operations: &mut Vec<Operation>) -> Self {
+        let result = match self {
+            Expression::Binary { operator: operator @ (BinaryOperator::And | BinaryOperator::Or), .. } => {
+                let operands = self.operands(*operator).into_iter()
+                    .map(|operand| operand.associative_law(operations))
+                    .collect::<Vec<_>>();
+                chain(*operator, operands).unwrap_or_else(|| self.clone())
+            }
+            Expression::Binary { left, operator, right } => binary(
+                left.associative_law(operations),
+                *operator,
+                right.associative_law(operations),
+            ),
+            Expression::Not(expr) => not(expr.associative_law(operations)),
+            atomic => atomic.clone(),
+        };
+        if let Some(operation) = Operation::new(self, &result, Law::AssociativeLaw) {
+            operations.push(operation);
+        }
+        result
+    }
+
+    /// Remove the constants that do not affect the result of the operator.
+    /// This is done by replacing `a ⋀ ⊤` with `a` and `a ⋁ ⊥` with `a`.
+    pub(super) fn identity_law(&self, operations: &mut Vec<Operation>) -> Self {
+        let result = match self {
+            Expression::Binary { left, operator, right } => {
+                let left = left.identity_law(operations);
+                let right = right.identity_law(operations);
+                match (operator, left, right) {
+                    (BinaryOperator::And, Expression::Constant(true), other)
+                    | (BinaryOperator::And, other, Expression::Constant(true))
+                    | (BinaryOperator::Or, Expression::Constant(false), other)
+                    | (BinaryOperator::Or, other, Expression::Constant(false)) => other,
+                    (operator, left, right) => binary(left, *operator, right),
+                }
+            }
+            Expression::Not(expr) => not(expr.identity_law(operations)),
+            atomic => atomic.clone(),
+        };
+        if let Some(operation) = Operation::new(self, &result, Law::IdentityLaw) {
+            operations.push(operation);
+        }
+        result
+    }
+
+    /// Replace the operator with the constant that decides its result.
+    /// This is done by replacing `a ⋀ ⊥` with `⊥` and `a ⋁ ⊤` with `⊤`.
+    pub(super) fn domination_law(&self, operations: &mut Vec<Operation>) -> Self {
+        let result = match self {
+            Expression::Binary { left, operator, right } => {
+                let left = left.domination_law(operations);
+                let right = right.domination_law(operations);
+                match (operator, &left, &right) {
+                    (BinaryOperator::And, Expression::Constant(false), _)
+                    | (BinaryOperator::And, _, Expression::Constant(false)) => Expression::Constant(false),
+                    (BinaryOperator::Or, Expression::Constant(true), _)
+                    | (BinaryOperator::Or, _, Expression::Constant(true)) => Expression::Constant(true),
+                    _ => binary(left, *operator, right),
+                }
+            }
+            Expression::Not(expr) => not(expr.domination_law(operations)),
+            atomic => atomic.clone(),
+        };
+        if let Some(operation) = Operation::new(self, &result, Law::DominationLaw) {
+            operations.push(operation);
+        }
+        result
+    }
+
+    /// Replace chains containing both an expression and its negation with a constant.
+    /// This is done by replacing `a ⋀ ¬a` with `⊥`, `a ⋁ ¬a` with `⊤`, `¬⊤` with `⊥` and `¬⊥` with `⊤`.
+    pub(super) fn complement_law(&self, operations: &mut Vec<Operation>, ignore_case: bool) -> Self {
+        let result = match self {
+            Expression::Binary { operator: operator @ (BinaryOperator::And | BinaryOperator::Or), .. }
+            if self.has_opposite_operands(*operator, ignore_case) => {
+                Expression::Constant(*operator == BinaryOperator::Or)
+            }
+            Expression::Binary { left, operator, right } => binary(
+                left.complement_law(operations, ignore_case),
+                *operator,
+                right.complement_law(operations, ignore_case),
+            ),
+            Expression::Not(expr) => match expr.deref() {
+                Expression::Constant(value) => Expression::Constant(!value),
+                _ => not(expr.complement_law(operations, ignore_case)),
+            },
+            atomic => atomic.clone(),
+        };
+        if let Some(operation) = Operation::new(self, &result, Law::ComplementLaw) {
+            operations.push(operation);
+        }
+        result
+    }
+
+    fn has_opposite_operands(&self, operator: BinaryOperator, ignore_case: bool) -> bool {
+        let operands = self.operands(operator);
+        operands.iter().enumerate()
+            .any(|(index, operand)| operands[..index].iter().any(|other| operand.opposite_eq(other, ignore_case)))
     }
 
     fn distribution_law(&self, operations: &mut Vec<Operation>) -> Self {
@@ -212,8 +501,34 @@ impl Expression {
         result
     }
 
+    /// Sort the operands of commutative operators into a canonical order.
+    /// This is done by replacing `b ⋀ a` with `a ⋀ b`, see [`Expression::canonical_cmp`] for the order.
     fn commutative_law(&self, operations: &mut Vec<Operation>) -> Self {
-        todo!("? | Order of operands does not matter in AND and OR operations.")
+        let result = match self {
+            Expression::Binary { left, operator, right } if operator.is_commutative() => {
+                let operands = if operator.is_associative() {
+                    self.operands(*operator)
+                } else {
+                    vec![left.as_ref(), right.as_ref()]
+                };
+                let mut operands = operands.into_iter()
+                    .map(|operand| operand.commutative_law(operations))
+                    .collect::<Vec<_>>();
+                operands.sort_by(Expression::canonical_cmp);
+                chain(*operator, operands).unwrap_or_else(|| self.clone())
+            }
+            Expression::Binary { left, operator, right } => binary(
+                left.commutative_law(operations),
+                *operator,
+                right.commutative_law(operations),
+            ),
+            Expression::Not(expr) => not(expr.commutative_law(operations)),
+            atomic => atomic.clone(),
+        };
+        if let Some(operation) = Operation::new(self, &result, Law::CommutativeLaw) {
+            operations.push(operation);
+        }
+        result
     }
 }
 
@@ -239,12 +554,14 @@ fn evaluate_equals_or_opposites<F: Fn(Expression, Expression) -> Expression>(
 
 #[cfg(test)]
 mod tests {
-    use crate::expressions::helpers::{and, atomic, implies, not, or};
-    use crate::expressions::simplify::Law;
+    use crate::expressions::helpers::{and, atomic, constant, iff, implies, nand, nor, not, or, xor};
+    use crate::expressions::helpers::chain;
+    use crate::expressions::operator::BinaryOperator;
+    use crate::expressions::simplify::{Law, LimitExceeded, Options, Strategy};
 
     #[test]
     fn test_simplify() {
-        let (expression, operations) = implies(atomic("a"), atomic("b")).simplify(Default::default());
+        let (expression, operations) = implies(atomic("a"), atomic("b")).simplify(Default::default()).unwrap();
         assert_eq!(expression, or(not(atomic("a")), atomic("b")));
         assert_eq!(operations.len(), 1);
         assert_eq!(operations[0].law, Law::EliminationOfImplication);
@@ -252,15 +569,66 @@ mod tests {
 
     #[test]
     fn test_simplify_a_and_a() {
-        let (expression, operations) = and(atomic("a"), atomic("a")).simplify(Default::default());
+        let (expression, operations) = and(atomic("a"), atomic("a")).simplify(Default::default()).unwrap();
         assert_eq!(expression, atomic("a"));
         assert_eq!(operations.len(), 1);
         assert_eq!(operations[0].law, Law::AbsorptionLaw);
     }
 
+    // A & !!A <=> A & A <=> A
+    #[test]
+    fn test_simplify_until_unchanged() {
+        let (expression, operations) = and(atomic("a"), not(not(atomic("a")))).simplify(Default::default()).unwrap();
+        assert_eq!(expression, atomic("a"));
+        assert_eq!(operations.len(), 3);
+        assert_eq!(operations[0].law, Law::DoubleNegationElimination);
+        assert_eq!(operations[2].law, Law::AbsorptionLaw);
+        assert_eq!(operations[2].before.to_string(), "a ⋀ a");
+    }
+
+    #[test]
+    fn test_simplify_max_iterations() {
+        let options = Options { max_iterations: 1, ..Default::default() };
+        let (expression, operations) = and(atomic("a"), not(not(atomic("a")))).simplify(options).unwrap();
+        assert_eq!(expression, and(atomic("a"), atomic("a")));
+        assert!(operations.into_iter().map(|operation| operation.law).all(|law| law == Law::DoubleNegationElimination));
+    }
+
+    #[test]
+    fn test_simplify_zero_iterations() {
+        let options = Options { max_iterations: 0, ..Default::default() };
+        let (expression, operations) = implies(atomic("a"), atomic("b")).simplify(options).unwrap();
+        assert_eq!(expression, implies(atomic("a"), atomic("b")));
+        assert!(operations.is_empty());
+    }
+
+    #[test]
+    fn test_simplify_minimal_sop() {
+        let options = Options { strategy: Strategy::MinimalSop, ..Default::default() };
+        let expression = or(or(and(atomic("a"), atomic("b")), and(not(atomic("a")), atomic("c"))), and(atomic("b"), atomic("c")));
+        let (expression, operations) = expression.simplify(options).unwrap();
+        assert_eq!(expression, or(and(atomic("a"), atomic("b")), and(not(atomic("a")), atomic("c"))));
+        assert!(operations.is_empty());
+    }
+
+    #[test]
+    fn test_simplify_minimal_sop_too_many_atomic_values() {
+        let options = Options { strategy: Strategy::MinimalSop, ..Default::default() };
+        let expression = chain(BinaryOperator::Or, ["a", "b", "c", "d", "e", "f", "g", "h", "i", "j", "k", "l", "m"].map(atomic)).unwrap();
+        assert_eq!(expression.simplify(options).unwrap_err(), LimitExceeded::AtomicValues { limit: 12 });
+    }
+
+    #[test]
+    fn test_simplify_espresso() {
+        let options = Options { strategy: Strategy::Espresso, ..Default::default() };
+        let (expression, operations) = or(and(atomic("a"), atomic("b")), and(atomic("a"), not(atomic("b")))).simplify(options).unwrap();
+        assert_eq!(expression, atomic("a"));
+        assert!(operations.is_empty());
+    }
+
     #[test]
     fn test_implication_and_de_morgans() {
-        let expression = implies(and(not(atomic("a")), atomic("b")), atomic("c")).simplify(Default::default()).0;
+        let expression = implies(and(not(atomic("a")), atomic("b")), atomic("c")).simplify(Default::default()).unwrap().0;
         assert_eq!(expression, or(or(atomic("a"), not(atomic("b"))), atomic("c")));
     }
 
@@ -271,8 +639,8 @@ mod tests {
         assert_eq!(expression, or(not(atomic("a")), atomic("b")));
         assert_eq!(operations.len(), 1);
         assert_eq!(operations[0].law, Law::EliminationOfImplication);
-        assert_eq!(operations[0].before, "a ➔ b");
-        assert_eq!(operations[0].after, "¬a ⋁ b");
+        assert_eq!(operations[0].before.to_string(), "a ➔ b");
+        assert_eq!(operations[0].after.to_string(), "¬a ⋁ b");
     }
 
     #[test]
@@ -282,11 +650,11 @@ mod tests {
         assert_eq!(expression, or(not(atomic("a")), or(not(atomic("b")), atomic("c"))));
         assert_eq!(operations.len(), 2);
         assert_eq!(operations[0].law, Law::EliminationOfImplication);
-        assert_eq!(operations[0].before, "b ➔ c");
-        assert_eq!(operations[0].after, "¬b ⋁ c");
+        assert_eq!(operations[0].before.to_string(), "b ➔ c");
+        assert_eq!(operations[0].after.to_string(), "¬b ⋁ c");
         assert_eq!(operations[1].law, Law::EliminationOfImplication);
-        assert_eq!(operations[1].before, "a ➔ b ➔ c");
-        assert_eq!(operations[1].after, "¬a ⋁ ¬b ⋁ c");
+        assert_eq!(operations[1].before.to_string(), "a ➔ (b ➔ c)");
+        assert_eq!(operations[1].after.to_string(), "¬a ⋁ (¬b ⋁ c)");
     }
 
     #[test]
@@ -303,6 +671,80 @@ mod tests {
         assert_eq!(expression, or(atomic("a"), and(atomic("b"), atomic("c"))));
     }
 
+    #[test]
+    fn test_elimination_of_biconditional() {
+        let mut operations = vec![];
+        let expression = iff(atomic("a"), atomic("b")).elimination_of_biconditional(&mut operations);
+        assert_eq!(expression, and(implies(atomic("a"), atomic("b")), implies(atomic("b"), atomic("a"))));
+        assert_eq!(operations.len(), 1);
+        assert_eq!(operations[0].law, Law::EliminationOfBiconditional);
+        assert_eq!(operations[0].before.to_string(), "a ⇔ b");
+        assert_eq!(operations[0].after.to_string(), "(a ➔ b) ⋀ (b ➔ a)");
+    }
+
+    #[test]
+    fn test_elimination_of_biconditional_nested() {
+        let mut operations = vec![];
+        let expression = or(atomic("a"), not(iff(atomic("b"), atomic("c")))).elimination_of_biconditional(&mut operations);
+        assert_eq!(expression, or(atomic("a"), not(and(implies(atomic("b"), atomic("c")), implies(atomic("c"), atomic("b"))))));
+        assert_eq!(operations.len(), 3);
+    }
+
+    #[test]
+    fn test_simplify_biconditional() {
+        let (expression, operations) = iff(atomic("a"), atomic("a")).simplify(Default::default()).unwrap();
+        assert_eq!(expression, constant(true));
+        assert_eq!(operations[0].law, Law::EliminationOfBiconditional);
+    }
+
+    #[test]
+    fn test_elimination_of_exclusive_or() {
+        let mut operations = vec![];
+        let expression = xor(atomic("a"), atomic("b")).elimination_of_exclusive_or(&mut operations);
+        assert_eq!(expression, or(and(atomic("a"), not(atomic("b"))), and(not(atomic("a")), atomic("b"))));
+        assert_eq!(operations.len(), 1);
+        assert_eq!(operations[0].law, Law::EliminationOfExclusiveOr);
+        assert_eq!(operations[0].before.to_string(), "a ⊕ b");
+        assert_eq!(operations[0].after.to_string(), "a ⋀ ¬b ⋁ ¬a ⋀ b");
+    }
+
+    #[test]
+    fn test_elimination_of_nand() {
+        let mut operations = vec![];
+        let expression = nand(atomic("a"), atomic("b")).elimination_of_nand(&mut operations);
+        assert_eq!(expression, not(and(atomic("a"), atomic("b"))));
+        assert_eq!(operations.len(), 1);
+        assert_eq!(operations[0].law, Law::EliminationOfNand);
+    }
+
+    #[test]
+    fn test_elimination_of_nor_nested() {
+        let mut operations = vec![];
+        let expression = nor(nor(atomic("a"), atomic("b")), atomic("c")).elimination_of_nor(&mut operations);
+        assert_eq!(expression, not(or(not(or(atomic("a"), atomic("b"))), atomic("c"))));
+        assert_eq!(operations.len(), 2);
+        assert!(operations.into_iter().map(|operation| operation.law).all(|law| law == Law::EliminationOfNor));
+    }
+
+    #[test]
+    fn test_simplify_nand_with_itself() {
+        let (expression, _) = nand(atomic("a"), atomic("a")).simplify(Default::default()).unwrap();
+        assert_eq!(expression, not(atomic("a")));
+    }
+
+    #[test]
+    fn test_simplify_xor_with_itself() {
+        let (expression, _) = xor(atomic("a"), atomic("a")).simplify(Default::default()).unwrap();
+        assert_eq!(expression, constant(false));
+    }
+
+    #[test]
+    fn test_commutative_law_nand_not_associative() {
+        let mut operations = vec![];
+        let expression = nand(atomic("c"), nand(atomic("b"), atomic("a"))).commutative_law(&mut operations);
+        assert_eq!(expression, nand(atomic("c"), nand(atomic("a"), atomic("b"))));
+    }
+
     #[test]
     fn test_double_negation_elimination() {
         let mut operations = vec![];
@@ -310,8 +752,8 @@ mod tests {
         assert_eq!(expression, atomic("a"));
         assert_eq!(operations.len(), 1);
         assert_eq!(operations[0].law, Law::DoubleNegationElimination);
-        assert_eq!(operations[0].before, "¬¬a");
-        assert_eq!(operations[0].after, "a");
+        assert_eq!(operations[0].before.to_string(), "¬¬a");
+        assert_eq!(operations[0].after.to_string(), "a");
     }
 
     #[test]
@@ -321,8 +763,8 @@ mod tests {
         assert_eq!(expression, not(atomic("a")));
         assert_eq!(operations.len(), 1);
         assert_eq!(operations[0].law, Law::DoubleNegationElimination);
-        assert_eq!(operations[0].before, "¬¬¬a");
-        assert_eq!(operations[0].after, "¬a");
+        assert_eq!(operations[0].before.to_string(), "¬¬¬a");
+        assert_eq!(operations[0].after.to_string(), "¬a");
     }
 
     #[test]
@@ -332,11 +774,11 @@ mod tests {
         assert_eq!(expression, not(atomic("a")));
         assert_eq!(operations.len(), 2);
         assert_eq!(operations[0].law, Law::DoubleNegationElimination);
-        assert_eq!(operations[0].before, "¬¬¬a");
-        assert_eq!(operations[0].after, "¬a");
+        assert_eq!(operations[0].before.to_string(), "¬¬¬a");
+        assert_eq!(operations[0].after.to_string(), "¬a");
         assert_eq!(operations[1].law, Law::DoubleNegationElimination);
-        assert_eq!(operations[1].before, "¬¬¬¬¬a");
-        assert_eq!(operations[1].after, "¬a");
+        assert_eq!(operations[1].before.to_string(), "¬¬¬¬¬a");
+        assert_eq!(operations[1].after.to_string(), "¬a");
     }
 
     #[test]
@@ -362,8 +804,8 @@ mod tests {
         assert_eq!(expression, or(not(atomic("a")), not(atomic("b"))));
         assert_eq!(operations.len(), 1);
         assert_eq!(operations[0].law, Law::DeMorgansLaws);
-        assert_eq!(operations[0].before, "¬(a ⋀ b)");
-        assert_eq!(operations[0].after, "¬a ⋁ ¬b");
+        assert_eq!(operations[0].before.to_string(), "¬(a ⋀ b)");
+        assert_eq!(operations[0].after.to_string(), "¬a ⋁ ¬b");
     }
 
     #[test]
@@ -373,8 +815,8 @@ mod tests {
         assert_eq!(expression, and(not(atomic("a")), not(atomic("b"))));
         assert_eq!(operations.len(), 1);
         assert_eq!(operations[0].law, Law::DeMorgansLaws);
-        assert_eq!(operations[0].before, "¬((a ⋁ b))");
-        assert_eq!(operations[0].after, "¬a ⋀ ¬b");
+        assert_eq!(operations[0].before.to_string(), "¬(a ⋁ b)");
+        assert_eq!(operations[0].after.to_string(), "¬a ⋀ ¬b");
     }
 
     #[test]
@@ -453,8 +895,8 @@ mod tests {
         assert_eq!(simplified, atomic("A"));
         assert_eq!(operations.len(), 1);
         assert_eq!(operations[0].law, Law::AbsorptionLaw);
-        assert_eq!(operations[0].before, "A ⋀ A");
-        assert_eq!(operations[0].after, "A");
+        assert_eq!(operations[0].before.to_string(), "A ⋀ A");
+        assert_eq!(operations[0].after.to_string(), "A");
     }
 
     // (A | B) & !A <=> B & !A
@@ -465,6 +907,216 @@ mod tests {
         assert_eq!(expression, and(atomic("b"), not(atomic("a"))));
     }
 
+    // A & B & A <=> A & B
+    #[test]
+    fn test_absorption_law_duplicate_in_chain() {
+        let mut operations = vec![];
+        let expression = and(and(atomic("a"), atomic("b")), atomic("a")).absorption_law(&mut operations, Default::default());
+        assert_eq!(expression, and(atomic("a"), atomic("b")));
+        assert_eq!(operations.len(), 1);
+        assert_eq!(operations[0].law, Law::AbsorptionLaw);
+    }
+
+    #[test]
+    fn test_absorption_law_duplicate_in_chain_ignore_case() {
+        let mut operations = vec![];
+        let expression = or(or(atomic("a"), atomic("b")), atomic("A")).absorption_law(&mut operations, true);
+        assert_eq!(expression, or(atomic("a"), atomic("b")));
+    }
+
+    #[test]
+    fn test_associative_law_and() {
+        let mut operations = vec![];
+        let expression = and(atomic("a"), and(atomic("b"), atomic("c"))).associative_law(&mut operations);
+        assert_eq!(expression, and(and(atomic("a"), atomic("b")), atomic("c")));
+        assert_eq!(operations.len(), 1);
+        assert_eq!(operations[0].law, Law::AssociativeLaw);
+    }
+
+    #[test]
+    fn test_associative_law_or() {
+        let mut operations = vec![];
+        let expression = or(or(atomic("a"), atomic("b")), or(atomic("c"), atomic("d"))).associative_law(&mut operations);
+        assert_eq!(expression, or(or(or(atomic("a"), atomic("b")), atomic("c")), atomic("d")));
+        assert_eq!(operations.len(), 1);
+    }
+
+    #[test]
+    fn test_associative_law_left_associated_none() {
+        let mut operations = vec![];
+        let expression = and(and(atomic("a"), atomic("b")), atomic("c")).associative_law(&mut operations);
+        assert_eq!(expression, and(and(atomic("a"), atomic("b")), atomic("c")));
+        assert!(operations.is_empty());
+    }
+
+    #[test]
+    fn test_associative_law_mixed_operators_none() {
+        let mut operations = vec![];
+        let expression = and(atomic("a"), or(atomic("b"), atomic("c"))).associative_law(&mut operations);
+        assert_eq!(expression, and(atomic("a"), or(atomic("b"), atomic("c"))));
+        assert!(operations.is_empty());
+    }
+
+    #[test]
+    fn test_associative_law_nested() {
+        let mut operations = vec![];
+        let expression = or(atomic("a"), not(and(atomic("b"), and(atomic("c"), atomic("d"))))).associative_law(&mut operations);
+        assert_eq!(expression, or(atomic("a"), not(and(and(atomic("b"), atomic("c")), atomic("d")))));
+        assert_eq!(operations.len(), 3);
+        assert!(operations.into_iter().map(|operation| operation.law).all(|law| law == Law::AssociativeLaw));
+    }
+
+    // A & (B & A) <=> A & B
+    #[test]
+    fn test_simplify_duplicate_across_nesting() {
+        let (expression, operations) = and(atomic("a"), and(atomic("b"), atomic("a"))).simplify(Default::default()).unwrap();
+        assert_eq!(expression, and(atomic("a"), atomic("b")));
+        assert_eq!(operations[0].law, Law::AssociativeLaw);
+        assert_eq!(operations[1].law, Law::AbsorptionLaw);
+    }
+
+    #[test]
+    fn test_commutative_law_and() {
+        let mut operations = vec![];
+        let expression = and(atomic("b"), atomic("a")).commutative_law(&mut operations);
+        assert_eq!(expression, and(atomic("a"), atomic("b")));
+        assert_eq!(operations.len(), 1);
+        assert_eq!(operations[0].law, Law::CommutativeLaw);
+        assert_eq!(operations[0].before.to_string(), "b ⋀ a");
+        assert_eq!(operations[0].after.to_string(), "a ⋀ b");
+    }
+
+    #[test]
+    fn test_commutative_law_sorted_none() {
+        let mut operations = vec![];
+        let expression = or(or(atomic("a"), not(atomic("a"))), atomic("b")).commutative_law(&mut operations);
+        assert_eq!(expression, or(or(atomic("a"), not(atomic("a"))), atomic("b")));
+        assert!(operations.is_empty());
+    }
+
+    #[test]
+    fn test_commutative_law_nested() {
+        let mut operations = vec![];
+        let expression = and(or(atomic("c"), atomic("b")), atomic("a")).commutative_law(&mut operations);
+        assert_eq!(expression, and(atomic("a"), or(atomic("b"), atomic("c"))));
+        assert_eq!(operations.len(), 2);
+        assert!(operations.into_iter().map(|operation| operation.law).all(|law| law == Law::CommutativeLaw));
+    }
+
+    #[test]
+    fn test_commutative_law_implication_none() {
+        let mut operations = vec![];
+        let expression = implies(atomic("b"), atomic("a")).commutative_law(&mut operations);
+        assert_eq!(expression, implies(atomic("b"), atomic("a")));
+    }
+
+    // (A | B) & (B | A) <=> A | B
+    #[test]
+    fn test_absorption_law_duplicate_different_order() {
+        let mut operations = vec![];
+        let expression = and(or(atomic("a"), atomic("b")), or(atomic("b"), atomic("a"))).absorption_law(&mut operations, false);
+        assert_eq!(expression, or(atomic("a"), atomic("b")));
+    }
+
+    #[test]
+    fn test_simplify_canonical_order() {
+        let (expression, operations) = or(atomic("b"), and(atomic("a"), atomic("a"))).simplify(Default::default()).unwrap();
+        assert_eq!(expression, or(atomic("a"), atomic("b")));
+        assert_eq!(operations.last().unwrap().law, Law::CommutativeLaw);
+    }
+
+    #[test]
+    fn test_identity_law_and() {
+        let mut operations = vec![];
+        let expression = and(atomic("a"), constant(true)).identity_law(&mut operations);
+        assert_eq!(expression, atomic("a"));
+        assert_eq!(operations.len(), 1);
+        assert_eq!(operations[0].law, Law::IdentityLaw);
+        assert_eq!(operations[0].before.to_string(), "a ⋀ ⊤");
+        assert_eq!(operations[0].after.to_string(), "a");
+    }
+
+    #[test]
+    fn test_identity_law_or() {
+        let mut operations = vec![];
+        let expression = or(constant(false), or(atomic("a"), constant(false))).identity_law(&mut operations);
+        assert_eq!(expression, atomic("a"));
+    }
+
+    #[test]
+    fn test_identity_law_only_constants() {
+        let mut operations = vec![];
+        let expression = and(constant(true), constant(true)).identity_law(&mut operations);
+        assert_eq!(expression, constant(true));
+    }
+
+    #[test]
+    fn test_domination_law_and() {
+        let mut operations = vec![];
+        let expression = and(and(atomic("a"), constant(false)), atomic("b")).domination_law(&mut operations);
+        assert_eq!(expression, constant(false));
+        assert_eq!(operations.len(), 2);
+        assert!(operations.into_iter().map(|operation| operation.law).all(|law| law == Law::DominationLaw));
+    }
+
+    #[test]
+    fn test_domination_law_or() {
+        let mut operations = vec![];
+        let expression = or(atomic("a"), constant(true)).domination_law(&mut operations);
+        assert_eq!(expression, constant(true));
+    }
+
+    #[test]
+    fn test_domination_law_identity_none() {
+        let mut operations = vec![];
+        let expression = or(atomic("a"), constant(false)).domination_law(&mut operations);
+        assert_eq!(expression, or(atomic("a"), constant(false)));
+        assert!(operations.is_empty());
+    }
+
+    #[test]
+    fn test_complement_law_and() {
+        let mut operations = vec![];
+        let expression = and(and(atomic("a"), atomic("b")), not(atomic("a"))).complement_law(&mut operations, false);
+        assert_eq!(expression, constant(false));
+        assert_eq!(operations.len(), 1);
+        assert_eq!(operations[0].law, Law::ComplementLaw);
+        assert_eq!(operations[0].before.to_string(), "a ⋀ b ⋀ ¬a");
+        assert_eq!(operations[0].after.to_string(), "⊥");
+    }
+
+    #[test]
+    fn test_complement_law_or() {
+        let mut operations = vec![];
+        let expression = or(not(atomic("a")), atomic("A")).complement_law(&mut operations, true);
+        assert_eq!(expression, constant(true));
+    }
+
+    #[test]
+    fn test_complement_law_negated_constant() {
+        let mut operations = vec![];
+        let expression = not(constant(true)).complement_law(&mut operations, false);
+        assert_eq!(expression, constant(false));
+    }
+
+    #[test]
+    fn test_simplify_tautology() {
+        let (expression, _) = implies(atomic("a"), or(atomic("a"), atomic("b"))).simplify(Default::default()).unwrap();
+        assert_eq!(expression, constant(true));
+    }
+
+    #[test]
+    fn test_simplify_contradiction() {
+        let (expression, _) = and(atomic("a"), not(or(atomic("a"), atomic("b")))).simplify(Default::default()).unwrap();
+        assert_eq!(expression, constant(false));
+    }
+
+    #[test]
+    fn test_simplify_constants() {
+        let (expression, _) = or(and(atomic("a"), constant(true)), not(constant(true))).simplify(Default::default()).unwrap();
+        assert_eq!(expression, atomic("a"));
+    }
+
     #[test]
     fn test_distributive_law_and() {
         let mut operations = vec![];
@@ -0,0 +1,213 @@
+use std::collections::HashMap;
+use std::fmt::Write;
+
+use serde::Deserialize;
+
+use crate::expressions::expression::Expression;
+
+const NODE_RADIUS: f64 = 18.0;
+const HORIZONTAL_SPACING: f64 = 48.0;
+const VERTICAL_SPACING: f64 = 64.0;
+const MARGIN: f64 = 24.0;
+
+/// How a [`SyntaxTree`] is rendered.
+#[derive(Debug, Default, Copy, Clone, PartialEq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TreeFormat {
+    /// The Graphviz DOT language, which can be laid out by Graphviz.
+    #[default]
+    #[serde(alias = "DOT")]
+    Dot,
+    /// An SVG image, laid out without any external tools.
+    #[serde(alias = "SVG")]
+    Svg,
+}
+
+/// The nodes of an expression, with an edge from every operator to its operands.
+/// If subtrees are shared, equal subexpressions are a single node, so the tree becomes a directed acyclic graph.
+#[derive(Debug)]
+pub struct SyntaxTree {
+    /// The nodes in the order they are first reached from the root, so the root is the first node.
+    nodes: Vec<TreeNode>,
+}
+
+#[derive(Debug)]
+struct TreeNode {
+    label: String,
+    children: Vec<usize>,
+}
+
+impl SyntaxTree {
+    pub fn new(expression: &Expression, shared: bool) -> Self {
+        let mut tree = Self { nodes: vec![] };
+        tree.add(expression, shared.then(HashMap::new).as_mut());
+        tree
+    }
+
+    fn add<'a>(&mut self, expression: &'a Expression, mut shared: Option<&mut HashMap<&'a Expression, usize>>) -> usize {
+        if let Some(index) = shared.as_ref().and_then(|shared| shared.get(expression)) {
+            return *index;
+        }
+        let index = self.nodes.len();
+        let (label, operands) = match expression {
+            Expression::Not(operand) => ("¬".to_string(), vec![operand.as_ref()]),
+            Expression::Binary { left, operator, right } => (operator.symbol().to_string(), vec![left.as_ref(), right.as_ref()]),
+            Expression::Atomic(_) | Expression::Constant(_) => (expression.to_string(), vec![]),
+        };
+        self.nodes.push(TreeNode { label, children: vec![] });
+        if let Some(shared) = shared.as_mut() {
+            shared.insert(expression, index);
+        }
+        for operand in operands {
+            let child = self.add(operand, shared.as_deref_mut());
+            self.nodes[index].children.push(child);
+        }
+        index
+    }
+
+    /// Renders the tree in the Graphviz DOT language, where the operands of an operator are ordered from left to right.
+    pub fn to_dot(&self) -> String {
+        let mut dot = String::from("digraph {\n    node [shape=circle];\n");
+        for (index, node) in self.nodes.iter().enumerate() {
+            let label = node.label.replace('\\', "\\\\").replace('"', "\\\"");
+            let _ = writeln!(dot, "    {index} [label=\"{label}\"];");
+        }
+        for (index, node) in self.nodes.iter().enumerate() {
+            for child in &node.children {
+                let _ = writeln!(dot, "    {index} -> {child};");
+            }
+        }
+        dot.push('}');
+        dot
+    }
+
+    /// Renders the tree as an SVG image, with the root at the top.
+    /// Every node is placed one level below its deepest parent, and the leaves are placed from left to right,
+    /// with every operator centered above its operands.
+    pub fn to_svg(&self) -> String {
+        let mut depths = vec![None; self.nodes.len()];
+        self.assign_depth(0, 0, &mut depths);
+        let depths = depths.into_iter().map(Option::unwrap_or_default).collect::<Vec<_>>();
+        let mut columns = vec![None; self.nodes.len()];
+        let mut leaves = 0;
+        self.assign_column(0, &mut columns, &mut leaves);
+        let position = |index: usize| (
+            MARGIN + NODE_RADIUS + columns[index].unwrap_or_default() * HORIZONTAL_SPACING,
+            MARGIN + NODE_RADIUS + depths[index] as f64 * VERTICAL_SPACING,
+        );
+        let width = 2.0 * (MARGIN + NODE_RADIUS) + (leaves.max(1) - 1) as f64 * HORIZONTAL_SPACING;
+        let height = 2.0 * (MARGIN + NODE_RADIUS) + depths.iter().max().copied().unwrap_or_default() as f64 * VERTICAL_SPACING;
+
+        let mut svg = format!(
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{width}\" height=\"{height}\" viewBox=\"0 0 {width} {height}\">\n"
+        );
+        svg.push_str("<g stroke=\"black\">\n");
+        for (index, node) in self.nodes.iter().enumerate() {
+            let (x1, y1) = position(index);
+            for child in &node.children {
+                let (x2, y2) = position(*child);
+                let _ = writeln!(svg, "<line x1=\"{x1}\" y1=\"{y1}\" x2=\"{x2}\" y2=\"{y2}\"/>");
+            }
+        }
+        svg.push_str("</g>\n<g font-family=\"sans-serif\" font-size=\"14\" text-anchor=\"middle\" dominant-baseline=\"central\">\n");
+        for (index, node) in self.nodes.iter().enumerate() {
+            let (x, y) = position(index);
+            let _ = writeln!(svg, "<circle cx=\"{x}\" cy=\"{y}\" r=\"{NODE_RADIUS}\" fill=\"white\" stroke=\"black\"/>");
+            let _ = writeln!(svg, "<text x=\"{x}\" y=\"{y}\">{}</text>", escape_xml(&node.label));
+        }
+        svg.push_str("</g>\n</svg>");
+        svg
+    }
+
+    /// Places the node at least one level below every parent.
+    fn assign_depth(&self, index: usize, depth: usize, depths: &mut [Option<usize>]) {
+        if depths[index].is_some_and(|current| current >= depth) {
+            return;
+        }
+        depths[index] = Some(depth);
+        for child in &self.nodes[index].children {
+            self.assign_depth(*child, depth + 1, depths);
+        }
+    }
+
+    /// Places the leaves in the next free column, and the operators centered above their operands.
+    fn assign_column(&self, index: usize, columns: &mut [Option<f64>], leaves: &mut usize) -> f64 {
+        if let Some(column) = columns[index] {
+            return column;
+        }
+        let children = &self.nodes[index].children;
+        let column = if children.is_empty() {
+            *leaves += 1;
+            (*leaves - 1) as f64
+        } else {
+            let sum = children.iter().map(|child| self.assign_column(*child, columns, leaves)).sum::<f64>();
+            sum / children.len() as f64
+        };
+        columns[index] = Some(column);
+        column
+    }
+}
+
+fn escape_xml(text: &str) -> String {
+    text.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;").replace('"', "&quot;")
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::expressions::helpers::{and, atomic, constant, implies, not, or};
+    use crate::expressions::syntax_tree::SyntaxTree;
+
+    #[test]
+    fn test_to_dot() {
+        let tree = SyntaxTree::new(&and(atomic("a"), not(atomic("b"))), false);
+        assert_eq!(tree.to_dot(), "digraph {\n    node [shape=circle];\n    \
+            0 [label=\"⋀\"];\n    1 [label=\"a\"];\n    2 [label=\"¬\"];\n    3 [label=\"b\"];\n    \
+            0 -> 1;\n    0 -> 2;\n    2 -> 3;\n}");
+    }
+
+    #[test]
+    fn test_shared_subtrees() {
+        let shared = or(atomic("a"), atomic("b"));
+        let expression = implies(and(shared.clone(), atomic("c")), not(shared));
+        assert_eq!(SyntaxTree::new(&expression, false).nodes.len(), 10);
+        let tree = SyntaxTree::new(&expression, true);
+        assert_eq!(tree.nodes.len(), 7);
+        assert_eq!(tree.to_dot().matches("-> 2;").count(), 2);
+    }
+
+    #[test]
+    fn test_to_svg() {
+        let tree = SyntaxTree::new(&or(atomic("a"), and(atomic("b"), constant(true))), false);
+        let svg = tree.to_svg();
+        assert!(svg.starts_with("<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"180\" height=\"212\""));
+        assert!(svg.ends_with("</svg>"));
+        assert_eq!(svg.matches("<circle").count(), 5);
+        assert_eq!(svg.matches("<line").count(), 4);
+        assert!(svg.contains(">⊤</text>"));
+    }
+
+    #[test]
+    fn test_to_svg_places_operator_above_operands() {
+        let svg = SyntaxTree::new(&and(atomic("a"), atomic("b")), false).to_svg();
+        assert!(svg.contains("<circle cx=\"66\" cy=\"42\""));
+        assert!(svg.contains("<circle cx=\"42\" cy=\"106\""));
+        assert!(svg.contains("<circle cx=\"90\" cy=\"106\""));
+    }
+
+    #[test]
+    fn test_to_svg_shared_node_is_below_every_parent() {
+        let shared = atomic("a");
+        let expression = and(not(not(shared.clone())), shared);
+        let svg = SyntaxTree::new(&expression, true).to_svg();
+        assert_eq!(svg.matches("<circle").count(), 4);
+        assert!(svg.contains("cy=\"234\""));
+    }
+
+    #[test]
+    fn test_escape() {
+        let svg = SyntaxTree::new(&atomic("<a&b>"), false).to_svg();
+        assert!(svg.contains(">&lt;a&amp;b&gt;</text>"));
+        let dot = SyntaxTree::new(&atomic("a\"b"), false).to_dot();
+        assert!(dot.contains("[label=\"a\\\"b\"]"));
+    }
+}
@@ -1,20 +1,32 @@
 use std::cmp::Ordering;
 use std::collections::HashMap;
+use std::fmt::Write;
 
 use lib::map;
 use lib::vector::distinct::Distinct;
 use serde::{Deserialize, Serialize};
 
 use crate::expressions::expression::Expression;
+use crate::expressions::minimize::DontCares;
+use crate::expressions::notation::Notation;
 use crate::routing::options::TruthTableOptions;
 
 type TruthMatrix = Vec<Vec<bool>>;
 
+/// The most atomic values a [`TruthTable::joint`] can have, since there is a row for every combination of them.
+pub const MAX_JOINT_ATOMICS: usize = 16;
+
 #[derive(Debug, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct TruthTable {
+    /// The expression of each column, which the header is written from.
+    #[serde(skip)]
+    columns: Vec<Expression>,
     header: Vec<String>,
     truth_matrix: TruthMatrix,
+    /// Whether each row of the truth matrix is a don't-care, only present if there are don't-cares.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    dont_care_rows: Option<Vec<bool>>,
 }
 
 #[derive(Debug, Default, Copy, Clone, Deserialize)]
@@ -37,15 +49,121 @@ pub enum Sort {
 
 impl TruthTable {
     pub fn new(expression: &Expression, options: TruthTableOptions) -> Self {
-        let mut header = Self::extract_header(expression);
+        let mut columns = Self::extract_header(expression);
+        let header = columns.iter().map(Expression::to_string).collect::<Vec<_>>();
         let mut truth_matrix = Self::generate_truth_matrix(expression, &header, options.hide, options.hide_intermediate_steps);
         if !matches!(options.sort, Sort::Default) {
             Self::sort_matrix(&mut truth_matrix, options.sort);
         }
         if options.hide_intermediate_steps {
-            header = Self::remove_non_atomic_from_header(&header);
+            columns = Self::remove_non_atomic_from_header(columns);
+        }
+        Self::from_columns(columns, truth_matrix)
+    }
+
+    fn from_columns(columns: Vec<Expression>, truth_matrix: TruthMatrix) -> Self {
+        let header = columns.iter().map(Expression::to_string).collect();
+        Self { columns, header, truth_matrix, dont_care_rows: None }
+    }
+
+    /// Creates a truth table where the rows that are `dont_cares` are marked.
+    pub fn with_dont_cares(expression: &Expression, options: TruthTableOptions, dont_cares: &DontCares) -> Self {
+        let mut table = Self::new(expression, options);
+        if dont_cares.is_empty() {
+            return table;
+        }
+        let mut atomics = expression.get_atomic_values().into_iter().collect::<Vec<_>>();
+        atomics.sort();
+        let minterms = dont_cares.minterms(&atomics);
+        let columns = atomics.iter()
+            .filter_map(|atomic| table.header.iter().position(|column| column == atomic))
+            .collect::<Vec<_>>();
+        table.dont_care_rows = Some(table.truth_matrix.iter()
+            .map(|row| columns.iter().fold(0, |minterm, column| minterm << 1 | row[*column] as u64))
+            .map(|minterm| minterms.binary_search(&minterm).is_ok())
+            .collect());
+        table
+    }
+
+    /// Creates a truth table with a column for every atomic value of the expressions, ordered by name,
+    /// followed by a column for each of the expressions in the given order.
+    /// Returns `None` if the expressions have more than [`MAX_JOINT_ATOMICS`] atomic values together.
+    pub fn joint(expressions: &[Expression]) -> Option<Self> {
+        let mut atomics = expressions.iter()
+            .flat_map(Expression::get_atomic_values)
+            .collect::<Vec<_>>();
+        atomics.sort();
+        atomics.dedup();
+        if atomics.len() > MAX_JOINT_ATOMICS {
+            return None;
+        }
+        let truth_matrix = Self::truth_combinations(atomics.len() as u32).into_iter()
+            .map(|combo| {
+                let booleans = atomics.iter().cloned().zip(combo.iter().copied()).collect();
+                let values = expressions.iter()
+                    .map(|expression| Self::_resolve_expression(expression, &booleans)[expression])
+                    .collect::<Vec<_>>();
+                [combo, values].concat()
+            })
+            .collect();
+        let columns = atomics.into_iter()
+            .map(Expression::Atomic)
+            .chain(expressions.iter().cloned())
+            .collect();
+        Some(Self::from_columns(columns, truth_matrix))
+    }
+
+    pub fn header(&self) -> &[String] {
+        &self.header
+    }
+
+    /// Writes the header in the given notation.
+    pub fn with_notation(mut self, notation: Notation) -> Self {
+        self.header = self.columns.iter().map(|column| column.to_string_in(notation)).collect();
+        self
+    }
+
+    /// Writes the table as a LaTeX `tabular`, where the last column is separated by a vertical line.
+    /// True and false are written as `T` and `F`, and the last column of a don't-care row is written as `X`.
+    pub fn to_latex(&self) -> String {
+        let columns = self.header.len().saturating_sub(1);
+        let mut latex = format!("\\begin{{tabular}}{{{}|c}}\n  ", "c".repeat(columns));
+        latex.push_str(&self.columns.iter()
+            .map(|column| format!("${}$", column.to_latex()))
+            .collect::<Vec<_>>()
+            .join(" & "));
+        latex.push_str(" \\\\\n  \\hline\n");
+        for (index, row) in self.truth_matrix.iter().enumerate() {
+            let dont_care = self.dont_care_rows.as_ref().is_some_and(|rows| rows[index]);
+            let cells = row.iter()
+                .enumerate()
+                .map(|(column, value)| match (dont_care && column == columns, value) {
+                    (true, _) => "X",
+                    (false, true) => "T",
+                    (false, false) => "F",
+                })
+                .collect::<Vec<_>>();
+            let _ = writeln!(latex, "  {} \\\\", cells.join(" & "));
         }
-        Self { header, truth_matrix }
+        latex.push_str("\\end{tabular}");
+        latex
+    }
+
+    /// Finds the rows where the expression is true, as minterm indices.
+    /// The first of the `atomics` is the most significant bit of the index, and a set bit means the value is true.
+    /// The minterms are returned in ascending order.
+    pub fn minterms(expression: &Expression, atomics: &[String]) -> Vec<u64> {
+        let mask = (1 << atomics.len()) - 1;
+        let mut minterms = Self::truth_combinations(atomics.len() as u32).iter()
+            .enumerate()
+            .filter(|(_, combo)| {
+                let booleans = atomics.iter().cloned().zip(combo.iter().copied()).collect();
+                Self::_resolve_expression(expression, &booleans)[expression]
+            })
+            .map(|(index, _)| !(index as u64) & mask)
+            .collect::<Vec<_>>();
+        minterms.sort();
+        minterms
     }
 
     fn sort_matrix(truth_matrix: &mut TruthMatrix, sort: Sort) {
@@ -56,61 +174,50 @@ impl TruthTable {
         })
     }
 
-    fn remove_non_atomic_from_header(header: &[String]) -> Vec<String> {
-        header.iter()
+    fn remove_non_atomic_from_header(columns: Vec<Expression>) -> Vec<Expression> {
+        let last = columns.len().saturating_sub(1);
+        columns.into_iter()
             .enumerate()
-            .filter_map(|(index, s)| {
-                if !Self::contains_operator(s) || index == header.len() - 1 {
-                    Some(s.clone())
-                } else {
-                    None
-                }
-            })
+            .filter_map(|(index, column)| (column.is_atomic() || index == last).then_some(column))
             .collect()
     }
 
-    fn contains_operator(slice: &str) -> bool {
-        slice.contains('⋀') || slice.contains('⋁') || slice.contains('➔')
-    }
-
     /// Extracts the header for the truth table from the expression
     /// Duplicate values are removed.
     /// - Arguments
     ///     - `expression` - The expression to extract the header from
     /// - Returns
-    ///     - A vector of strings representing the header
+    ///     - The expression of each column of the header
     /// # Example
     /// ```
     /// let expression = TruthTable::extract_header(&atomic("A"));
-    /// let complex_expression = TruthTable::extract_header(&implies(and(atomic("A"), atomic("B")), or(atomic("C"), atomic("D"))));
-    /// assert_eq!(expression, vec!["A"]);
-    /// assert_eq!(complex_expression, vec!["A", "B", "A ⋀ B", "C", "D", "(C ⋁ D)", "A ⋀ B ➔ (C ⋁ D)"]);
+    /// let complex_expression = TruthTable::extract_header(&implies(and(atomic("A"), atomic("B")), or(atomic("C"), atomic("D"))))
+    ///     .iter().map(Expression::to_string).collect::<Vec<_>>();
+    /// assert_eq!(expression, vec![atomic("A")]);
+    /// assert_eq!(complex_expression, vec!["A", "B", "A ⋀ B", "C", "D", "C ⋁ D", "A ⋀ B ➔ C ⋁ D"]);
     /// ```
-    fn extract_header(expression: &Expression) -> Vec<String> {
+    fn extract_header(expression: &Expression) -> Vec<Expression> {
         match expression {
             Expression::Not(expr) => {
                 let mut header = Self::extract_header(expr);
-                header.push(expression.to_string());
+                header.push(expression.clone());
                 header.distinct();
                 header
             }
             Expression::Binary { left, right, .. } => {
                 let mut header = Self::extract_header(left);
                 header.extend(Self::extract_header(right));
-                header.push(expression.to_string());
+                header.push(expression.clone());
                 header.distinct();
                 header
             }
-            Expression::Atomic(value) => vec![value.clone()],
+            Expression::Atomic(_) | Expression::Constant(_) => vec![expression.clone()],
         }
     }
 
     fn generate_truth_matrix(expression: &Expression, header: &[String], hide: Hide, hide_intermediate: bool) -> TruthMatrix {
         let mut atomics = expression.get_atomic_values()
             .into_iter().collect::<Vec<String>>();
-        if atomics.is_empty() {
-            return vec![];
-        }
         atomics.sort();
         Self::truth_combinations(atomics.len() as u32).iter()
             .filter_map(|combo| {
@@ -192,6 +299,7 @@ impl TruthTable {
                     unreachable!("Atomic value not found in booleans")
                 }
             }
+            Expression::Constant(value) => map!(expression => *value),
         }
     }
 }
@@ -200,7 +308,7 @@ impl TruthTable {
 mod tests {
     use lib::matrix;
 
-    use crate::expressions::helpers::{and, atomic, implies, not, or};
+    use crate::expressions::helpers::{and, atomic, constant, iff, implies, not, or};
 
     use super::*;
 
@@ -223,6 +331,79 @@ mod tests {
         ]);
     }
 
+    #[test]
+    fn test_minterms() {
+        let expression = or(and(atomic("A"), not(atomic("B"))), atomic("C"));
+        let atomics = ["A".to_string(), "B".to_string(), "C".to_string()];
+        assert_eq!(TruthTable::minterms(&expression, &atomics), vec![1, 3, 4, 5, 7]);
+    }
+
+    #[test]
+    fn test_with_dont_cares() {
+        let expression = and(atomic("A"), atomic("B"));
+        let dont_cares = DontCares { minterms: vec![1], expression: Some(and(atomic("A"), not(atomic("B")))) };
+        let truth_table = TruthTable::with_dont_cares(&expression, Default::default(), &dont_cares);
+        assert_eq!(truth_table.dont_care_rows, Some(vec![false, true, true, false]));
+    }
+
+    #[test]
+    fn test_with_dont_cares_sorted() {
+        let expression = and(atomic("A"), atomic("B"));
+        let dont_cares = DontCares { minterms: vec![0], expression: None };
+        let options = TruthTableOptions { sort: Sort::FalseFirst, ..Default::default() };
+        let truth_table = TruthTable::with_dont_cares(&expression, options, &dont_cares);
+        assert_eq!(truth_table.truth_matrix.last(), Some(&vec![true, true, true]));
+        assert_eq!(truth_table.dont_care_rows, Some(vec![false, false, true, false]));
+    }
+
+    #[test]
+    fn test_with_notation() {
+        let truth_table = TruthTable::new(&implies(atomic("A"), not(atomic("B"))), Default::default());
+        assert_eq!(truth_table.with_notation(Notation::Word).header, vec!["A", "B", "NOT B", "A IMPLIES NOT B"]);
+    }
+
+    #[test]
+    fn test_to_latex() {
+        let truth_table = TruthTable::new(&implies(atomic("A"), not(atomic("B"))), Default::default());
+        assert_eq!(truth_table.to_latex(), "\\begin{tabular}{ccc|c}\n  \
+            $A$ & $B$ & $\\neg B$ & $A \\to \\neg B$ \\\\\n  \\hline\n  \
+            T & T & F & F \\\\\n  T & F & T & T \\\\\n  F & T & F & T \\\\\n  F & F & T & T \\\\\n\
+            \\end{tabular}");
+    }
+
+    #[test]
+    fn test_to_latex_dont_cares() {
+        let expression = and(atomic("A"), atomic("B"));
+        let dont_cares = DontCares { minterms: vec![3], expression: None };
+        let truth_table = TruthTable::with_dont_cares(&expression, Default::default(), &dont_cares);
+        assert!(truth_table.to_latex().contains("  T & T & X \\\\\n"));
+    }
+
+    #[test]
+    fn test_joint() {
+        let expressions = [implies(atomic("B"), atomic("A")), atomic("B")];
+        let truth_table = TruthTable::joint(&expressions).unwrap();
+        assert_eq!(truth_table.header, vec!["A", "B", "B ➔ A", "B"]);
+        assert_eq!(truth_table.truth_matrix, matrix![
+            true, true, true, true;
+            true, false, true, false;
+            false, true, false, true;
+            false, false, true, false
+        ]);
+    }
+
+    #[test]
+    fn test_joint_too_many_atomic_values() {
+        let expressions = (0..=MAX_JOINT_ATOMICS).map(|index| atomic(format!("x{index:02}"))).collect::<Vec<_>>();
+        assert!(TruthTable::joint(&expressions).is_none());
+    }
+
+    #[test]
+    fn test_minterms_constant() {
+        assert_eq!(TruthTable::minterms(&constant(true), &[]), vec![0]);
+        assert!(TruthTable::minterms(&constant(false), &[]).is_empty());
+    }
+
     #[test]
     fn test_new_truth_table_a_and_b_or_c() {
         let expression = and(or(atomic("A"), atomic("C")), or(atomic("B"), atomic("C")));
@@ -443,7 +624,7 @@ mod tests {
     fn test_resolve_expression_even_more_duplicates() {
         let expression = and(atomic("A"), and(atomic("A"), and(atomic("A"), atomic("A"))));
         let booleans = HashMap::from([("A".into(), true)]);
-        let header = vec!["A".into(), "A ⋀ A".into(), "A ⋀ A ⋀ A".into(), "A ⋀ A ⋀ A ⋀ A".into()];
+        let header = vec!["A".into(), "A ⋀ A".into(), "A ⋀ (A ⋀ A)".into(), "A ⋀ (A ⋀ (A ⋀ A))".into()];
         let values = TruthTable::resolve_expression(&expression, &booleans, &header, false);
         assert_eq!(values, vec![true, true, true, true]);
     }
@@ -465,56 +646,104 @@ mod tests {
     #[test]
     fn test_atomic_expression() {
         let expression = atomic("A");
-        let header = TruthTable::extract_header(&expression);
+        let header = TruthTable::extract_header(&expression).iter().map(Expression::to_string).collect::<Vec<_>>();
         assert_eq!(header, vec!["A"]);
     }
 
     #[test]
     fn test_not_expression() {
         let expression = not(atomic("A"));
-        let header = TruthTable::extract_header(&expression);
+        let header = TruthTable::extract_header(&expression).iter().map(Expression::to_string).collect::<Vec<_>>();
         assert_eq!(header, vec!["A", "¬A"]);
     }
 
     #[test]
     fn test_binary_and_expression() {
         let expression = and(atomic("A"), atomic("B"));
-        let header = TruthTable::extract_header(&expression);
+        let header = TruthTable::extract_header(&expression).iter().map(Expression::to_string).collect::<Vec<_>>();
         assert_eq!(header, vec!["A", "B", "A ⋀ B"]);
     }
 
     #[test]
     fn test_binary_or_expression() {
         let expression = or(atomic("A"), atomic("B"));
-        let header = TruthTable::extract_header(&expression);
+        let header = TruthTable::extract_header(&expression).iter().map(Expression::to_string).collect::<Vec<_>>();
         assert_eq!(header, vec!["A", "B", "A ⋁ B"]);
     }
 
     #[test]
     fn test_binary_implies_expression() {
         let expression = implies(atomic("A"), atomic("B"));
-        let header = TruthTable::extract_header(&expression);
+        let header = TruthTable::extract_header(&expression).iter().map(Expression::to_string).collect::<Vec<_>>();
         assert_eq!(header, vec!["A", "B", "A ➔ B"]);
     }
 
     #[test]
     fn test_complex_expression() {
         let expression = implies(and(atomic("A"), atomic("B")), or(atomic("C"), atomic("D")));
-        let header = TruthTable::extract_header(&expression);
-        assert_eq!(header, vec!["A", "B", "A ⋀ B", "C", "D", "C ⋁ D", "A ⋀ B ➔ (C ⋁ D)"]);
+        let header = TruthTable::extract_header(&expression).iter().map(Expression::to_string).collect::<Vec<_>>();
+        assert_eq!(header, vec!["A", "B", "A ⋀ B", "C", "D", "C ⋁ D", "A ⋀ B ➔ C ⋁ D"]);
     }
 
     #[test]
     fn test_equal_expressions_should_not_duplicate() {
         let expression = and(atomic("A"), and(atomic("A"), and(atomic("A"), atomic("A"))));
-        let header = TruthTable::extract_header(&expression);
-        assert_eq!(header, vec!["A", "A ⋀ A", "A ⋀ A ⋀ A", "A ⋀ A ⋀ A ⋀ A"]);
+        let header = TruthTable::extract_header(&expression).iter().map(Expression::to_string).collect::<Vec<_>>();
+        assert_eq!(header, vec!["A", "A ⋀ A", "A ⋀ (A ⋀ A)", "A ⋀ (A ⋀ (A ⋀ A))"]);
     }
 
     #[test]
     fn test_somewhat_equal() {
         let expression = and(atomic("A"), and(or(not(atomic("A")), atomic("B")), atomic("A")));
-        let header = TruthTable::extract_header(&expression);
-        assert_eq!(header, vec!["A", "¬A", "B", "¬A ⋁ B", "(¬A ⋁ B) ⋀ A", "A ⋀ (¬A ⋁ B) ⋀ A"]);
+        let header = TruthTable::extract_header(&expression).iter().map(Expression::to_string).collect::<Vec<_>>();
+        assert_eq!(header, vec!["A", "¬A", "B", "¬A ⋁ B", "(¬A ⋁ B) ⋀ A", "A ⋀ ((¬A ⋁ B) ⋀ A)"]);
+    }
+
+    #[test]
+    fn test_constant_expression() {
+        let expression = and(atomic("A"), constant(true));
+        let header = TruthTable::extract_header(&expression).iter().map(Expression::to_string).collect::<Vec<_>>();
+        assert_eq!(header, vec!["A", "⊤", "A ⋀ ⊤"]);
+    }
+
+    #[test]
+    fn test_new_truth_table_with_constant() {
+        let expression = or(atomic("A"), constant(false));
+        let truth_table = TruthTable::new(&expression, Default::default());
+        assert_eq!(truth_table.truth_matrix, matrix![
+            true, false, true;
+            false, false, false
+        ]);
+    }
+
+    #[test]
+    fn test_new_truth_table_only_constants() {
+        let expression = and(constant(true), not(constant(false)));
+        let truth_table = TruthTable::new(&expression, Default::default());
+        assert_eq!(truth_table.header, vec!["⊤", "⊥", "¬⊥", "⊤ ⋀ ¬⊥"]);
+        assert_eq!(truth_table.truth_matrix, matrix![true, false, true, true]);
+    }
+
+    #[test]
+    fn test_new_truth_table_iff() {
+        let expression = iff(atomic("A"), atomic("B"));
+        let truth_table = TruthTable::new(&expression, Default::default());
+        assert_eq!(truth_table.header, vec!["A", "B", "A ⇔ B"]);
+        assert_eq!(truth_table.truth_matrix, matrix![
+            true, true, true;
+            true, false, false;
+            false, true, false;
+            false, false, true
+        ]);
+    }
+
+    #[test]
+    fn test_new_truth_table_iff_hide_intermediate_steps() {
+        let expression = and(iff(atomic("A"), atomic("B")), atomic("C"));
+        let truth_table = TruthTable::new(&expression, TruthTableOptions {
+            hide_intermediate_steps: true,
+            ..Default::default()
+        });
+        assert_eq!(truth_table.header, vec!["A", "B", "C", "(A ⇔ B) ⋀ C"]);
     }
 }
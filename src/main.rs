@@ -13,7 +13,7 @@ mod utils;
 #[tokio::main]
 async fn main() {
     AppBuilder::new()
-        .routes(&[index::router(), simplify::router(), table::router()])
+        .routes(&[index::router(), simplify::router(), table::router(), cnf::router(), dnf::router(), nnf::router(), kmap::router(), sat::router(), classify::router(), equivalent::router(), entails::router(), models::router(), bdd::router(), tree::router()])
         .fallback(not_found)
         .cors(CorsLayer::permissive())
         .serve()
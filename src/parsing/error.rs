@@ -0,0 +1,139 @@
+use std::fmt::Display;
+
+use serde::Serialize;
+
+/// A description of why an expression could not be parsed, pointing at the position where parsing failed.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ParseError {
+    /// The position of the error, counted in characters from the start of the input.
+    pub offset: usize,
+    /// The position of the error, counted in bytes from the start of the input.
+    pub byte_offset: usize,
+    /// The token at the position of the error, or `None` if the end of the input was reached.
+    pub found: Option<String>,
+    /// The tokens that would have been accepted at the position of the error.
+    pub expected: Vec<String>,
+    /// The line containing the error, followed by a line with carets pointing at the offending token.
+    pub snippet: String,
+}
+
+impl ParseError {
+    /// Creates an error for the `input`, where `remaining` is the part of the input that could not be parsed.
+    /// Leading whitespace in `remaining` is skipped, so the error points at the offending token.
+    pub fn new(input: &str, remaining: &str, expected: Vec<String>) -> Self {
+        let remaining = remaining.trim_start();
+        let byte_offset = input.len() - remaining.len();
+        let found = Self::next_token(remaining);
+        Self {
+            offset: input[..byte_offset].chars().count(),
+            byte_offset,
+            snippet: Self::snippet(input, byte_offset, found.as_deref()),
+            found,
+            expected,
+        }
+    }
+
+    fn next_token(remaining: &str) -> Option<String> {
+        let is_word = |c: char| c.is_alphanumeric() || c == '_';
+        let is_symbol = |c: char| !c.is_whitespace() && !is_word(c) && c != '(' && c != ')';
+        let first = remaining.chars().next()?;
+        let token = if is_word(first) {
+            remaining.split(|c: char| !is_word(c)).next()
+        } else if is_symbol(first) {
+            remaining.split(|c: char| !is_symbol(c)).next()
+        } else {
+            remaining.get(..first.len_utf8())
+        };
+        token.map(Into::into)
+    }
+
+    /// Creates a caret-style snippet of the line containing the error.
+    /// # Example
+    /// ```text
+    /// a & or
+    ///     ^^
+    /// ```
+    fn snippet(input: &str, byte_offset: usize, found: Option<&str>) -> String {
+        let line_start = input[..byte_offset].rfind('\n').map_or(0, |index| index + 1);
+        let line_end = input[byte_offset..].find('\n').map_or(input.len(), |index| byte_offset + index);
+        let column = input[line_start..byte_offset].chars().count();
+        let width = found.map_or(1, |token| token.chars().count());
+        format!("{}\n{}{}", &input[line_start..line_end], " ".repeat(column), "^".repeat(width))
+    }
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.found {
+            Some(token) => write!(f, "Unexpected '{token}' at position {}", self.offset)?,
+            None => write!(f, "Unexpected end of input at position {}", self.offset)?,
+        }
+        if let Some((last, rest)) = self.expected.split_last() {
+            if rest.is_empty() {
+                write!(f, ", expected {last}")?;
+            } else {
+                write!(f, ", expected {} or {last}", rest.join(", "))?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ParseError;
+
+    #[test]
+    fn test_new_skips_whitespace() {
+        let error = ParseError::new("a  b", "  b", vec![]);
+        assert_eq!(error.offset, 3);
+        assert_eq!(error.byte_offset, 3);
+        assert_eq!(error.found, Some("b".into()));
+    }
+
+    #[test]
+    fn test_new_counts_characters() {
+        let error = ParseError::new("¬a ⋀ $", "$", vec![]);
+        assert_eq!(error.offset, 5);
+        assert_eq!(error.byte_offset, 8);
+    }
+
+    #[test]
+    fn test_found_end_of_input() {
+        let error = ParseError::new("a & ", "", vec![]);
+        assert_eq!(error.found, None);
+        assert_eq!(error.snippet, "a & \n    ^");
+    }
+
+    #[test]
+    fn test_found_symbol() {
+        let error = ParseError::new("a =/> b", "=/> b", vec![]);
+        assert_eq!(error.found, Some("=/>".into()));
+        assert_eq!(error.snippet, "a =/> b\n  ^^^");
+    }
+
+    #[test]
+    fn test_found_parenthesis() {
+        let error = ParseError::new("a ))", "))", vec![]);
+        assert_eq!(error.found, Some(")".into()));
+    }
+
+    #[test]
+    fn test_snippet_multiple_lines() {
+        let error = ParseError::new("a &\nb c", "c", vec![]);
+        assert_eq!(error.snippet, "b c\n  ^");
+    }
+
+    #[test]
+    fn test_display() {
+        let error = ParseError::new("a b", "b", vec!["operator".into(), "end of input".into()]);
+        assert_eq!(error.to_string(), "Unexpected 'b' at position 2, expected operator or end of input");
+    }
+
+    #[test]
+    fn test_display_end_of_input() {
+        let error = ParseError::new("!", "", vec!["atomic value".into(), "constant".into(), "(".into()]);
+        assert_eq!(error.to_string(), "Unexpected end of input at position 1, expected atomic value, constant or (");
+    }
+}
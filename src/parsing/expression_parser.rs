@@ -1,29 +1,104 @@
 use lib::nom::combinators::{exhausted, parenthesized, trim};
 use lib::nom::util::IntoResult;
 use nom::branch::alt;
-use nom::bytes::complete::{tag, take_while, take_while1};
-use nom::character::complete::char;
-use nom::combinator::{opt, peek};
-use nom::error::Error;
+use nom::bytes::complete::{tag, tag_no_case, take_while, take_while1};
+use nom::character::complete::{multispace0, one_of};
+use nom::combinator::{cut, map};
+use nom::error::{Error, ErrorKind};
 use nom::IResult;
 use nom::sequence::{pair, preceded};
 
 use crate::expressions::expression::Expression;
-use crate::expressions::helpers::{and, atomic, implies, not, or};
+use crate::expressions::helpers::{atomic, binary, constant, not};
+use crate::expressions::operator::BinaryOperator;
+use crate::parsing::error::ParseError;
+
+pub fn parse_expression(input: &str) -> Result<Expression, ParseError> {
+    exhausted(_parse_expression)(input).into_result().map_err(|error| {
+        let remaining = match error {
+            nom::Err::Error(error) | nom::Err::Failure(error) => error.input,
+            nom::Err::Incomplete(_) => "",
+        };
+        let parsed = &input[..input.len() - remaining.len()];
+        ParseError::new(input, remaining, expected_tokens(parsed))
+    })
+}
 
-pub fn parse_expression(input: &str) -> Result<Expression, nom::Err<Error<&str>>> {
-    exhausted(_parse_expression)(input).into_result()
+/// Finds the tokens that can follow the successfully `parsed` part of the input.
+/// An operand is expected at the start, and after an operator or an opening parenthesis,
+/// otherwise the operand is complete, and an operator or the end of the operand is expected.
+fn expected_tokens(parsed: &str) -> Vec<String> {
+    let parsed = parsed.trim_end();
+    let last_word = parsed.rsplit(|c: char| !is_identifier_char(c)).next().unwrap_or_default();
+    let operand_complete = if last_word.is_empty() {
+        parsed.ends_with([')', '⊤', '⊥'])
+    } else {
+        !KEYWORDS.iter()
+            .filter(|keyword| !matches!(**keyword, "true" | "false"))
+            .any(|keyword| last_word.eq_ignore_ascii_case(keyword))
+    };
+    let depth = parsed.matches('(').count().saturating_sub(parsed.matches(')').count());
+    let expected: &[&str] = match (operand_complete, depth) {
+        (true, 0) => &["operator", "end of input"],
+        (true, _) => &["operator", ")"],
+        (false, _) => &["atomic value", "constant", "¬", "("],
+    };
+    expected.iter().map(|token| token.to_string()).collect()
 }
 
+/// Words that are parsed as operators or constants, and therefore cannot be used as atomic values.
+const KEYWORDS: [&str; 10] = ["not", "and", "nand", "xor", "or", "nor", "implies", "iff", "true", "false"];
+
+/// Parses an expression, where the operators are ordered from the lowest to the highest precedence:
+/// `⇔`, `➔`, `⋁` and `↓`, `⊕`, `⋀` and `↑`, and finally `¬`.
+/// Operators with the same precedence are left associative, `a ➔ b ➔ c` is parsed as `(a ➔ b) ➔ c`.
+/// Every operator can be written using the symbol printed by this API, common alternatives, or a keyword in any casing.
 fn _parse_expression(input: &str) -> IResult<&str, Expression> {
-    let (remaining, atomic_expression) = left_hand_side(input)?;
-    if let (remaining, Some(complex_expression)) = opt(expression(atomic_expression.clone()))(remaining)? {
-        Ok((remaining, complex_expression))
-    } else {
-        Ok((remaining, atomic_expression))
+    biconditional_expression(input)
+}
+
+/// Parses one or more operands separated by operators of the same precedence.
+/// An operator must be followed by an operand, otherwise parsing fails at the position of the missing operand.
+fn binary_chain<'a, O, P>(operator: O, operand: P) -> impl Fn(&'a str) -> IResult<&'a str, Expression>
+where
+    O: Fn(&'a str) -> IResult<&'a str, BinaryOperator> + Copy,
+    P: Fn(&'a str) -> IResult<&'a str, Expression> + Copy,
+{
+    move |input: &'a str| {
+        let (mut remaining, mut expression) = operand(input)?;
+        loop {
+            match pair(trim(operator), cut(operand))(remaining) {
+                Ok((rest, (operator, right))) => {
+                    expression = binary(expression, operator, right);
+                    remaining = rest;
+                }
+                Err(nom::Err::Error(_)) => return Ok((remaining, expression)),
+                Err(error) => return Err(error),
+            }
+        }
     }
 }
 
+fn biconditional_expression(input: &str) -> IResult<&str, Expression> {
+    binary_chain(biconditional_operator, implication_expression)(input)
+}
+
+fn implication_expression(input: &str) -> IResult<&str, Expression> {
+    binary_chain(implication_operator, or_expression)(input)
+}
+
+fn or_expression(input: &str) -> IResult<&str, Expression> {
+    binary_chain(or_operator, xor_expression)(input)
+}
+
+fn xor_expression(input: &str) -> IResult<&str, Expression> {
+    binary_chain(xor_operator, and_expression)(input)
+}
+
+fn and_expression(input: &str) -> IResult<&str, Expression> {
+    binary_chain(and_operator, left_hand_side)(input)
+}
+
 fn left_hand_side(input: &str) -> IResult<&str, Expression> {
     alt((
         value,
@@ -32,108 +107,102 @@ fn left_hand_side(input: &str) -> IResult<&str, Expression> {
     ))(input)
 }
 
-fn expression<'a>(previous: Expression) -> impl Fn(&'a str) -> IResult<&'a str, Expression> {
-    move |input: &'a str| {
-        let (remaining, new) = operator_combinators(previous.clone())(input)?;
-        if !remaining.is_empty() {
-            expression(new.clone())(remaining)
-        } else {
-            Ok((remaining, new))
-        }
-    }
+fn parenthesized_expression(input: &str) -> IResult<&str, Expression> {
+    parenthesized(_parse_expression)(input)
 }
 
-fn operator_combinators(expression: Expression) -> impl Fn(&str) -> IResult<&str, Expression> {
-    move |input: &str| {
-        alt((
-            implication_expression(expression.clone()),
-            or_expression(expression.clone()),
-            and_expression(expression.clone()),
-            not_expression,
-        ))(input)
-    }
+fn not_expression(input: &str) -> IResult<&str, Expression> {
+    preceded(
+        pair(not_operator, multispace0),
+        cut(left_hand_side),
+    )(input).map(|(remaining, right)| {
+        (remaining, not(right))
+    })
 }
 
-fn parenthesized_expression(input: &str) -> IResult<&str, Expression> {
-    parenthesized(|input| {
-        let (remaining, atomic) = left_hand_side(input)?;
-        let (remaining, expression) = operator_combinators(atomic)(remaining)?;
-        if peek(trim(char(')')))(remaining).is_ok() {
-            Ok((remaining, expression))
-        } else {
-            operator_combinators(expression)(remaining)
-        }
-    })(input)
+fn not_operator(input: &str) -> IResult<&str, &str> {
+    alt((tag("!"), tag("¬"), tag("~"), keyword("not")))(input)
 }
 
-fn and_expression<'a>(previous: Expression) -> impl Fn(&'a str) -> IResult<&'a str, Expression> {
-    move |input: &'a str| {
-        preceded(
-            trim(char('&')),
-            left_hand_side,
-        )(input).map(|(remaining, right)| {
-            (remaining, and(previous.clone(), right))
-        })
-    }
+fn biconditional_operator(input: &str) -> IResult<&str, BinaryOperator> {
+    map(
+        alt((tag("<=>"), tag("<->"), tag("⇔"), tag("↔"), keyword("iff"))),
+        |_| BinaryOperator::Biconditional,
+    )(input)
 }
 
-fn complete_and(input: &str) -> IResult<&str, Expression> {
-    let (remaining, atomic) = value(input)?;
-    and_expression(atomic.clone())(remaining)
+fn implication_operator(input: &str) -> IResult<&str, BinaryOperator> {
+    map(
+        alt((tag("=>"), tag("->"), tag("➔"), tag("→"), keyword("implies"))),
+        |_| BinaryOperator::Implication,
+    )(input)
 }
 
-fn or_expression<'a>(previous: Expression) -> impl Fn(&'a str) -> IResult<&'a str, Expression> {
-    move |input: &'a str| {
-        preceded(
-            trim(char('|')),
-            alt((
-                complete_and,
-                left_hand_side,
-            )),
-        )(input).map(|(remaining, right)| {
-            (remaining, or(previous.clone(), right))
-        })
-    }
+fn or_operator(input: &str) -> IResult<&str, BinaryOperator> {
+    alt((
+        map(alt((tag("||"), tag("|"), tag("⋁"), tag("∨"), keyword("or"))), |_| BinaryOperator::Or),
+        map(alt((tag("↓"), keyword("nor"))), |_| BinaryOperator::Nor),
+    ))(input)
+}
+
+fn xor_operator(input: &str) -> IResult<&str, BinaryOperator> {
+    map(alt((tag("^"), tag("⊕"), tag("⊻"), keyword("xor"))), |_| BinaryOperator::Xor)(input)
 }
 
-fn complete_or(input: &str) -> IResult<&str, Expression> {
-    let (remaining, atomic) = value(input)?;
-    or_expression(atomic.clone())(remaining)
+fn and_operator(input: &str) -> IResult<&str, BinaryOperator> {
+    alt((
+        map(alt((tag("&&"), tag("&"), tag("⋀"), tag("∧"), keyword("and"))), |_| BinaryOperator::And),
+        map(alt((tag("↑"), keyword("nand"))), |_| BinaryOperator::Nand),
+    ))(input)
 }
 
-fn implication_expression<'a>(previous: Expression) -> impl Fn(&'a str) -> IResult<&'a str, Expression> {
+/// Parses the word in any casing, if it is not the beginning of a longer identifier.
+fn keyword<'a>(word: &'static str) -> impl Fn(&'a str) -> IResult<&'a str, &'a str> {
     move |input: &'a str| {
-        preceded(
-            trim(tag("=>")),
-            alt((
-                complete_and,
-                complete_or,
-                left_hand_side,
-            )),
-        )(input).map(|(remaining, right)| {
-            (remaining, implies(previous.clone(), right))
-        })
+        let (remaining, matched) = tag_no_case(word)(input)?;
+        if remaining.starts_with(is_identifier_char) {
+            Err(nom::Err::Error(Error::new(input, ErrorKind::Tag)))
+        } else {
+            Ok((remaining, matched))
+        }
     }
 }
 
-fn not_expression(input: &str) -> IResult<&str, Expression> {
-    preceded(
-        char('!'),
-        left_hand_side,
-    )(input).map(|(remaining, right)| {
-        (remaining, not(right))
-    })
+fn is_identifier_char(c: char) -> bool {
+    c.is_ascii_alphanumeric() || c == '_'
 }
 
 fn value(input: &str) -> IResult<&str, Expression> {
-    pair(
+    alt((
+        constant_symbol,
+        identifier,
+    ))(input)
+}
+
+fn constant_symbol(input: &str) -> IResult<&str, Expression> {
+    alt((
+        map(one_of("⊤1"), |_| constant(true)),
+        map(one_of("⊥0"), |_| constant(false)),
+    ))(input)
+}
+
+/// Parses an atomic value, or the constants `true` and `false` in any casing.
+/// The remaining [`KEYWORDS`] are operators, and are not accepted as atomic values.
+fn identifier(input: &str) -> IResult<&str, Expression> {
+    let (remaining, (first, rest)) = pair(
         take_while1(|c: char| c.is_ascii_alphabetic()),
-        take_while(|c: char| c.is_ascii_alphanumeric() || c == '_'),
-    )(input)
-        .map(|(remaining, (first, rest))| {
-            let value = format!("{first}{rest}");
-            (remaining, atomic(value))
-        })
+        take_while(is_identifier_char),
+    )(input)?;
+    let value = format!("{first}{rest}");
+    if value.eq_ignore_ascii_case("true") {
+        Ok((remaining, constant(true)))
+    } else if value.eq_ignore_ascii_case("false") {
+        Ok((remaining, constant(false)))
+    } else if KEYWORDS.iter().any(|keyword| value.eq_ignore_ascii_case(keyword)) {
+        Err(nom::Err::Error(Error::new(input, ErrorKind::Verify)))
+    } else {
+        Ok((remaining, atomic(value)))
+    }
 }
 
 #[cfg(test)]
@@ -182,6 +251,208 @@ mod tests {
         assert_eq!(result, Ok(implies(implies(atomic("a"), atomic("b")), atomic("c"))));
     }
 
+    #[test]
+    fn test_parse_biconditional() {
+        let input = "a <=> b";
+        let result = super::parse_expression(input);
+        assert_eq!(result, Ok(iff(atomic("a"), atomic("b"))));
+    }
+
+    #[test]
+    fn test_parse_biconditional_symbols() {
+        assert_eq!(super::parse_expression("a ⇔ b"), Ok(iff(atomic("a"), atomic("b"))));
+        assert_eq!(super::parse_expression("a ↔ b"), Ok(iff(atomic("a"), atomic("b"))));
+    }
+
+    #[test]
+    fn test_parse_biconditional_weight() {
+        let input = "a <=> b => c";
+        let result = super::parse_expression(input);
+        assert_eq!(result, Ok(iff(atomic("a"), implies(atomic("b"), atomic("c")))));
+    }
+
+    #[test]
+    fn test_parse_biconditional_parentheses() {
+        let input = "(a <=> b) & c";
+        let result = super::parse_expression(input);
+        assert_eq!(result, Ok(and(iff(atomic("a"), atomic("b")), atomic("c"))));
+    }
+
+    #[test]
+    fn test_operator_weight_and_before_or() {
+        let input = "a | b & c | d";
+        let result = super::parse_expression(input);
+        assert_eq!(result, Ok(or(or(atomic("a"), and(atomic("b"), atomic("c"))), atomic("d"))));
+    }
+
+    #[test]
+    fn test_operator_weight_or_before_implies() {
+        let input = "a => b & c | d";
+        let result = super::parse_expression(input);
+        assert_eq!(result, Ok(implies(atomic("a"), or(and(atomic("b"), atomic("c")), atomic("d")))));
+    }
+
+    #[test]
+    fn test_parse_xor() {
+        let input = "a ^ b ⊕ c";
+        let result = super::parse_expression(input);
+        assert_eq!(result, Ok(xor(xor(atomic("a"), atomic("b")), atomic("c"))));
+    }
+
+    #[test]
+    fn test_parse_xor_weight() {
+        let input = "a | b ^ c & d";
+        let result = super::parse_expression(input);
+        assert_eq!(result, Ok(or(atomic("a"), xor(atomic("b"), and(atomic("c"), atomic("d"))))));
+    }
+
+    #[test]
+    fn test_parse_nand() {
+        let input = "a ↑ b & c";
+        let result = super::parse_expression(input);
+        assert_eq!(result, Ok(and(nand(atomic("a"), atomic("b")), atomic("c"))));
+    }
+
+    #[test]
+    fn test_parse_nor() {
+        let input = "a ↓ b & c";
+        let result = super::parse_expression(input);
+        assert_eq!(result, Ok(nor(atomic("a"), and(atomic("b"), atomic("c")))));
+    }
+
+    #[test]
+    fn test_parse_missing_operand() {
+        let input = "a & ";
+        let result = super::parse_expression(input);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_parse_unicode_operators() {
+        let input = "¬a ⋀ b ⋁ c ➔ d ⇔ e";
+        let result = super::parse_expression(input);
+        assert_eq!(result, Ok(iff(implies(or(and(not(atomic("a")), atomic("b")), atomic("c")), atomic("d")), atomic("e"))));
+    }
+
+    #[test]
+    fn test_parse_alternative_symbols() {
+        let input = "~a ∧ b ∨ c → d";
+        let result = super::parse_expression(input);
+        assert_eq!(result, Ok(implies(or(and(not(atomic("a")), atomic("b")), atomic("c")), atomic("d"))));
+    }
+
+    #[test]
+    fn test_parse_programming_operators() {
+        let input = "!a && b || c -> d <-> e";
+        let result = super::parse_expression(input);
+        assert_eq!(result, Ok(iff(implies(or(and(not(atomic("a")), atomic("b")), atomic("c")), atomic("d")), atomic("e"))));
+    }
+
+    #[test]
+    fn test_parse_keywords() {
+        let input = "NOT a AND b OR c IMPLIES d IFF e";
+        let result = super::parse_expression(input);
+        assert_eq!(result, Ok(iff(implies(or(and(not(atomic("a")), atomic("b")), atomic("c")), atomic("d")), atomic("e"))));
+    }
+
+    #[test]
+    fn test_parse_keywords_any_casing() {
+        let input = "a xor b nand not(c) Nor d";
+        let result = super::parse_expression(input);
+        assert_eq!(result, Ok(nor(xor(atomic("a"), nand(atomic("b"), not(atomic("c")))), atomic("d"))));
+    }
+
+    #[test]
+    fn test_parse_keyword_prefix_is_atomic() {
+        let input = "notes & order";
+        let result = super::parse_expression(input);
+        assert_eq!(result, Ok(and(atomic("notes"), atomic("order"))));
+    }
+
+    #[test]
+    fn test_parse_keyword_is_not_atomic() {
+        let input = "a & or";
+        let result = super::parse_expression(input);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_parse_display_round_trip() {
+        let expressions = [
+            and(not(atomic("a")), or(atomic("b"), atomic("c"))),
+            implies(atomic("a"), not(and(atomic("b"), constant(false)))),
+            iff(atomic("a"), xor(atomic("b"), atomic("c"))),
+        ];
+        for expression in expressions {
+            let input = expression.to_string();
+            let result = super::parse_expression(&input);
+            assert_eq!(result, Ok(expression));
+        }
+    }
+
+    #[test]
+    fn test_error_missing_operand() {
+        let error = super::parse_expression("a & ").unwrap_err();
+        assert_eq!(error.offset, 4);
+        assert_eq!(error.found, None);
+        assert_eq!(error.expected, vec!["atomic value", "constant", "¬", "("]);
+    }
+
+    #[test]
+    fn test_error_missing_operator() {
+        let error = super::parse_expression("a b").unwrap_err();
+        assert_eq!(error.offset, 2);
+        assert_eq!(error.found, Some("b".into()));
+        assert_eq!(error.expected, vec!["operator", "end of input"]);
+        assert_eq!(error.snippet, "a b\n  ^");
+    }
+
+    #[test]
+    fn test_error_unclosed_parenthesis() {
+        let error = super::parse_expression("(a | b").unwrap_err();
+        assert_eq!(error.offset, 6);
+        assert_eq!(error.expected, vec!["operator", ")"]);
+    }
+
+    #[test]
+    fn test_error_inside_parentheses() {
+        let error = super::parse_expression("a & (b | ) => c").unwrap_err();
+        assert_eq!(error.offset, 9);
+        assert_eq!(error.found, Some(")".into()));
+        assert_eq!(error.expected, vec!["atomic value", "constant", "¬", "("]);
+    }
+
+    #[test]
+    fn test_error_keyword_as_operand() {
+        let error = super::parse_expression("a AND or").unwrap_err();
+        assert_eq!(error.offset, 6);
+        assert_eq!(error.found, Some("or".into()));
+        assert_eq!(error.expected, vec!["atomic value", "constant", "¬", "("]);
+    }
+
+    #[test]
+    fn test_error_after_keyword_operator() {
+        let error = super::parse_expression("NOT").unwrap_err();
+        assert_eq!(error.offset, 3);
+        assert_eq!(error.expected, vec!["atomic value", "constant", "¬", "("]);
+    }
+
+    #[test]
+    fn test_error_unknown_symbol() {
+        let error = super::parse_expression("a $ b").unwrap_err();
+        assert_eq!(error.offset, 2);
+        assert_eq!(error.found, Some("$".into()));
+        assert_eq!(error.to_string(), "Unexpected '$' at position 2, expected operator or end of input");
+    }
+
+    #[test]
+    fn test_error_empty() {
+        let error = super::parse_expression("").unwrap_err();
+        assert_eq!(error.offset, 0);
+        assert_eq!(error.found, None);
+        assert_eq!(error.expected, vec!["atomic value", "constant", "¬", "("]);
+    }
+
     #[test]
     fn test_parse_parentheses() {
         let input = "a & (b => c)";
@@ -266,6 +537,41 @@ mod tests {
         assert_eq!(result, Ok(atomic("a1")));
     }
 
+    #[test]
+    fn test_constant_symbols() {
+        let input = "⊤ & ⊥";
+        let result = super::parse_expression(input);
+        assert_eq!(result, Ok(and(constant(true), constant(false))));
+    }
+
+    #[test]
+    fn test_constant_words() {
+        let input = "a | TRUE & !false";
+        let result = super::parse_expression(input);
+        assert_eq!(result, Ok(or(atomic("a"), and(constant(true), not(constant(false))))));
+    }
+
+    #[test]
+    fn test_constant_digits() {
+        let input = "1 => 0";
+        let result = super::parse_expression(input);
+        assert_eq!(result, Ok(implies(constant(true), constant(false))));
+    }
+
+    #[test]
+    fn test_atomic_starting_with_constant_word() {
+        let input = "trueish";
+        let result = super::parse_expression(input);
+        assert_eq!(result, Ok(atomic("trueish")));
+    }
+
+    #[test]
+    fn test_multiple_digits_is_invalid() {
+        let input = "10";
+        let result = super::parse_expression(input);
+        assert!(result.is_err());
+    }
+
     #[test]
     fn test_empty() {
         let input = "";
@@ -289,33 +595,29 @@ mod tests {
 
     #[test]
     fn test_expression_and() {
-        let expression = atomic("a");
-        let input = " & b";
-        let result = super::expression(expression)(input);
+        let input = "a & b";
+        let result = super::_parse_expression(input);
         assert_eq!(result, Ok(("", and(atomic("a"), atomic("b")))));
     }
 
     #[test]
     fn test_expression_and_or() {
-        let expression = atomic("a");
-        let input = " & b | c";
-        let result = super::expression(expression)(input);
+        let input = "a & b | c";
+        let result = super::_parse_expression(input);
         assert_eq!(result, Ok(("", or(and(atomic("a"), atomic("b")), atomic("c")))));
     }
 
     #[test]
     fn test_expression_and_or_implies() {
-        let expression = atomic("a");
-        let input = " & b | c => d";
-        let result = super::expression(expression)(input);
+        let input = "a & b | c => d";
+        let result = super::_parse_expression(input);
         assert_eq!(result, Ok(("", implies(or(and(atomic("a"), atomic("b")), atomic("c")), atomic("d")))));
     }
 
     #[test]
     fn test_expression_parentheses_or() {
-        let expression = atomic("a");
-        let input = " & (b | c) => d";
-        let result = super::expression(expression)(input);
+        let input = "a & (b | c) => d";
+        let result = super::_parse_expression(input);
         assert_eq!(result, Ok(("", implies(and(atomic("a"), or(atomic("b"), atomic("c"))), atomic("d")))));
     }
 
@@ -328,25 +630,22 @@ mod tests {
 
     #[test]
     fn test_expression_parentheses_implies() {
-        let expression = atomic("a");
-        let input = " & b | (c => d)";
-        let result = super::expression(expression)(input);
+        let input = "a & b | (c => d)";
+        let result = super::_parse_expression(input);
         assert_eq!(result, Ok(("", or(and(atomic("a"), atomic("b")), implies(atomic("c"), atomic("d"))))));
     }
 
     #[test]
     fn test_expression_nested_parentheses() {
-        let expression = atomic("a");
-        let input = " & (b | (c => d))";
-        let result = super::expression(expression)(input);
+        let input = "a & (b | (c => d))";
+        let result = super::_parse_expression(input);
         assert_eq!(result, Ok(("", and(atomic("a"), or(atomic("b"), implies(atomic("c"), atomic("d")))))));
     }
 
     #[test]
     fn test_parse_or() {
-        let expression = atomic("a");
-        let input = " | b";
-        let result = super::or_expression(expression)(input);
+        let input = "a | b";
+        let result = super::or_expression(input);
         assert_eq!(result, Ok(("", or(atomic("a"), atomic("b")))));
     }
 
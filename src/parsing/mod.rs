@@ -1 +1,2 @@
-pub(crate) mod expression_parser;
\ No newline at end of file
+pub(crate) mod error;
+pub(crate) mod expression_parser;
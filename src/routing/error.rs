@@ -2,6 +2,8 @@ use axum::Json;
 use axum::response::{IntoResponse, Response};
 use serde::Serialize;
 
+use crate::parsing::error::ParseError;
+
 #[derive(Serialize, Default)]
 #[serde(rename_all = "SCREAMING_SNAKE_CASE")]
 pub enum ErrorKind {
@@ -9,15 +11,21 @@ pub enum ErrorKind {
     InvalidExpression,
     /// The expression is too long.
     LimitExceeded,
+    /// An option has a value that does not fit the expression.
+    InvalidOption,
     /// Unexpected error.
     #[default]
     Unexpected,
 }
 
 #[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
 pub struct Error {
     pub message: String,
     pub kind: ErrorKind,
+    /// Where and why parsing failed, if the kind is [`ErrorKind::InvalidExpression`].
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub parse_error: Option<ParseError>,
 }
 
 impl Error {
@@ -25,6 +33,17 @@ impl Error {
         Self {
             message: message.into(),
             kind,
+            parse_error: None,
+        }
+    }
+}
+
+impl From<ParseError> for Error {
+    fn from(error: ParseError) -> Self {
+        Self {
+            message: error.to_string(),
+            kind: ErrorKind::InvalidExpression,
+            parse_error: Some(error),
         }
     }
 }
@@ -1,4 +1,5 @@
 pub(crate) mod response;
+pub(crate) mod request;
 pub(crate) mod error;
 pub(crate) mod routes;
 pub(crate) mod options;
\ No newline at end of file
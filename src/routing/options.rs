@@ -1,6 +1,28 @@
 use serde::Deserialize;
+use crate::expressions::bdd::VariableOrder;
+use crate::expressions::expression::Expression;
+use crate::expressions::minimize::DontCares;
+use crate::expressions::notation::Notation;
+use crate::expressions::simplify::{DEFAULT_MAX_ITERATIONS, Strategy};
+use crate::expressions::syntax_tree::TreeFormat;
 use crate::expressions::truth_table::{Hide, Sort};
-use crate::utils::serialize::{ret_true, deserialize_bool};
+use crate::routing::error::{Error, ErrorKind};
+use crate::utils::serialize::{ret_true, deserialize_bool, deserialize_from_str, deserialize_list};
+
+/// The highest `maxIterations` a client is allowed to request.
+pub const MAX_ITERATIONS_LIMIT: usize = 100;
+/// The most models a client is allowed to request at once.
+pub const MAX_MODELS_LIMIT: usize = 100;
+/// The highest `offset` a client is allowed to request models from, since every skipped model is found again.
+pub const MAX_MODELS_OFFSET: usize = 10_000;
+
+const fn default_max_iterations() -> usize {
+    DEFAULT_MAX_ITERATIONS
+}
+
+const fn default_models_limit() -> usize {
+    20
+}
 
 // TODO deserialize_bool should not be necessary
 #[derive(Deserialize)]
@@ -13,6 +35,16 @@ pub struct SimplifyOptions {
     pub simplify: bool,
     #[serde(default, deserialize_with = "deserialize_bool")]
     pub ignore_case: bool,
+    #[serde(
+        default = "default_max_iterations",
+        deserialize_with = "deserialize_from_str"
+    )]
+    pub max_iterations: usize,
+    #[serde(default)]
+    pub strategy: Strategy,
+    /// Whether to classify the expression as a tautology, contradiction or contingent.
+    #[serde(default, deserialize_with = "deserialize_bool")]
+    pub classify: bool,
 }
 
 #[derive(Deserialize, Default)]
@@ -26,6 +58,80 @@ pub struct TruthTableOptions {
     pub hide_intermediate_steps: bool,
 }
 
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ModelsOptions {
+    /// The number of models to skip.
+    #[serde(default, deserialize_with = "deserialize_from_str")]
+    pub offset: usize,
+    /// The most models to return.
+    #[serde(
+        default = "default_models_limit",
+        deserialize_with = "deserialize_from_str"
+    )]
+    pub limit: usize,
+}
+
+#[derive(Deserialize, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct BddOptions {
+    #[serde(default)]
+    pub order: VariableOrder,
+    /// Comma separated atomic values to quantify existentially, before the diagram is returned.
+    #[serde(default, deserialize_with = "deserialize_list")]
+    pub exists: Vec<String>,
+    /// Comma separated atomic values to quantify universally, before the diagram is returned.
+    #[serde(default, deserialize_with = "deserialize_list")]
+    pub for_all: Vec<String>,
+    /// Whether to convert the diagram back into an expression, which may be exponentially larger than the diagram.
+    #[serde(default, deserialize_with = "deserialize_bool")]
+    pub to_expression: bool,
+}
+
+/// How the result of simplifying an expression or generating a truth table is returned.
+#[derive(Debug, Default, Copy, Clone, PartialEq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Output {
+    #[default]
+    #[serde(alias = "JSON")]
+    Json,
+    /// The simplification steps as an `align*` environment, and the truth table as a `tabular`.
+    #[serde(alias = "LATEX")]
+    Latex,
+}
+
+#[derive(Deserialize, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct OutputOptions {
+    #[serde(default)]
+    pub output: Output,
+}
+
+#[derive(Deserialize, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct NotationOptions {
+    #[serde(default)]
+    pub notation: Notation,
+}
+
+#[derive(Deserialize, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct TreeOptions {
+    #[serde(default)]
+    pub format: TreeFormat,
+    /// Whether equal subexpressions are drawn as a single node.
+    #[serde(default, deserialize_with = "deserialize_bool")]
+    pub shared: bool,
+}
+
+#[derive(Deserialize, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct KarnaughMapOptions {
+    /// Whether to include the groups of a minimal sum of products.
+    #[serde(default, deserialize_with = "deserialize_bool")]
+    pub groups: bool,
+}
+
 #[derive(Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct SimplifyAndTableOptions {
@@ -34,3 +140,34 @@ pub struct SimplifyAndTableOptions {
     #[serde(flatten)]
     pub table_options: TruthTableOptions,
 }
+
+/// The assignments of the atomic values where the value of the expression does not matter.
+#[derive(Deserialize, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct DontCareOptions {
+    /// Comma separated minterm indices, where the first atomic value in alphabetical order is the most significant bit.
+    #[serde(default, deserialize_with = "deserialize_list")]
+    pub dont_cares: Vec<u64>,
+    /// An expression that is true for every don't-care assignment.
+    pub dont_care: Option<String>,
+}
+
+impl DontCareOptions {
+    /// Parses the don't-care expression, and checks that the don't-cares only use the atomic values of `expression`.
+    pub fn into_dont_cares(self, expression: &Expression) -> Result<DontCares, Error> {
+        let atomics = expression.get_atomic_values();
+        let limit = 1u64.checked_shl(atomics.len() as u32).unwrap_or(0).wrapping_sub(1);
+        if let Some(minterm) = self.dont_cares.iter().find(|minterm| **minterm > limit) {
+            let message = format!("The don't-care {minterm} is outside the truth table of {} rows", limit as u128 + 1);
+            return Err(Error::new(message, ErrorKind::InvalidOption));
+        }
+        let dont_care = self.dont_care.map(Expression::try_from).transpose()?;
+        if let Some(unknown) = dont_care.iter()
+            .flat_map(Expression::get_atomic_values)
+            .find(|value| !atomics.contains(value)) {
+            let message = format!("The don't-care expression uses '{unknown}', which is not in the expression");
+            return Err(Error::new(message, ErrorKind::InvalidOption));
+        }
+        Ok(DontCares { minterms: self.dont_cares, expression: dont_care })
+    }
+}
@@ -0,0 +1,18 @@
+use serde::Deserialize;
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EquivalentRequest {
+    pub left: String,
+    pub right: String,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EntailsRequest {
+    pub premises: Vec<String>,
+    pub conclusion: String,
+    /// Whether to include a truth table with a column for every premise and the conclusion.
+    #[serde(default)]
+    pub truth_table: bool,
+}
@@ -1,26 +1,51 @@
+use axum::http::header;
 use axum::response::{IntoResponse, Response};
 use lib::derive::IntoResponse;
-use serde::Serialize;
+use serde::ser::SerializeStruct;
+use serde::{Serialize, Serializer};
 
+use crate::expressions::bdd::{BddNode, NodeId};
+use crate::expressions::classify::Classification;
 use crate::expressions::expression::Expression;
+use crate::expressions::karnaugh_map::KarnaughMap;
+use crate::expressions::normal_form::Literal;
+use crate::expressions::notation::Notation;
+use crate::expressions::sat::Assignment;
 use crate::expressions::simplify::Law;
 use crate::expressions::truth_table::TruthTable;
 
-#[derive(Debug, PartialEq, Serialize)]
+/// A law applied to an expression, where the expressions are written in the notation when serialized.
+#[derive(Debug, PartialEq)]
 pub struct Operation {
-    pub before: String,
-    pub after: String,
+    pub before: Expression,
+    pub after: Expression,
     pub law: Law,
+    notation: Notation,
 }
 
 impl Operation {
     pub fn new(before: &Expression, after: &Expression, law: Law) -> Option<Self> {
         if before != after {
-            Some(Self { before: before.to_string(), after: after.to_string(), law })
+            Some(Self { before: before.clone(), after: after.clone(), law, notation: Notation::default() })
         } else {
             None
         }
     }
+
+    /// Writes the expressions in the given notation.
+    pub fn with_notation(self, notation: Notation) -> Self {
+        Self { notation, ..self }
+    }
+}
+
+impl Serialize for Operation {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut operation = serializer.serialize_struct("Operation", 3)?;
+        operation.serialize_field("before", &self.before.to_string_in(self.notation))?;
+        operation.serialize_field("after", &self.after.to_string_in(self.notation))?;
+        operation.serialize_field("law", &self.law)?;
+        operation.end()
+    }
 }
 
 #[derive(Serialize, IntoResponse)]
@@ -32,6 +57,31 @@ pub struct SimplifyResponse {
     pub expression: Expression,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub truth_table: Option<TruthTable>,
+    /// The classification of the expression before simplification, if requested.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub classification: Option<Classification>,
+}
+
+#[derive(Serialize, IntoResponse)]
+#[serde(rename_all = "camelCase")]
+pub struct CnfResponse {
+    pub before: String,
+    pub after: String,
+    pub operations: Vec<Operation>,
+    pub expression: Expression,
+    /// The conjunction of clauses, where each clause is a disjunction of literals.
+    pub clauses: Vec<Vec<Literal>>,
+}
+
+#[derive(Serialize, IntoResponse)]
+#[serde(rename_all = "camelCase")]
+pub struct DnfResponse {
+    pub before: String,
+    pub after: String,
+    pub operations: Vec<Operation>,
+    pub expression: Expression,
+    /// The disjunction of terms, where each term is a conjunction of literals.
+    pub terms: Vec<Vec<Literal>>,
 }
 
 #[derive(Serialize, IntoResponse)]
@@ -50,4 +100,108 @@ impl IsValidResponse {
 #[serde(rename_all = "camelCase")]
 pub struct TruthTableResponse {
     pub truth_table: TruthTable,
-}
\ No newline at end of file
+}
+
+#[derive(Serialize, IntoResponse)]
+#[serde(rename_all = "camelCase")]
+pub struct KarnaughMapResponse {
+    pub karnaugh_map: KarnaughMap,
+}
+
+#[derive(Serialize, IntoResponse)]
+#[serde(rename_all = "camelCase")]
+pub struct SatResponse {
+    pub expression: String,
+    pub satisfiable: bool,
+    /// Values for the atomic values that make the expression true, if it is satisfiable.
+    pub assignment: Option<Assignment>,
+}
+
+#[derive(Serialize, IntoResponse)]
+#[serde(rename_all = "camelCase")]
+pub struct ClassifyResponse {
+    pub expression: String,
+    #[serde(flatten)]
+    pub classification: Classification,
+}
+
+#[derive(Serialize, IntoResponse)]
+#[serde(rename_all = "camelCase")]
+pub struct EquivalentResponse {
+    pub left: String,
+    pub right: String,
+    pub equivalent: bool,
+    /// Values for the atomic values where the expressions differ, if they are not equivalent.
+    pub counterexample: Option<Assignment>,
+}
+
+#[derive(Serialize, IntoResponse)]
+#[serde(rename_all = "camelCase")]
+pub struct EntailsResponse {
+    pub premises: Vec<String>,
+    pub conclusion: String,
+    /// Whether the conclusion is true whenever every premise is true.
+    pub valid: bool,
+    /// Values for the atomic values where every premise is true and the conclusion is false, if the argument is invalid.
+    pub counter_model: Option<Assignment>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub truth_table: Option<EntailmentTable>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EntailmentTable {
+    #[serde(flatten)]
+    pub truth_table: TruthTable,
+    /// The indices of the premise columns in the header.
+    pub premise_columns: Vec<usize>,
+    /// The index of the conclusion column in the header.
+    pub conclusion_column: usize,
+}
+
+#[derive(Serialize, IntoResponse)]
+#[serde(rename_all = "camelCase")]
+pub struct ModelsResponse {
+    pub expression: String,
+    /// The assignments that make the expression true, from `offset` and at most `limit` of them.
+    pub models: Vec<Assignment>,
+    pub offset: usize,
+    pub limit: usize,
+    /// Whether there are more models after these.
+    pub has_more: bool,
+}
+
+#[derive(Serialize, IntoResponse)]
+#[serde(rename_all = "camelCase")]
+pub struct CountModelsResponse {
+    pub expression: String,
+    /// The number of assignments that make the expression true.
+    pub count: u128,
+}
+
+#[derive(Serialize, IntoResponse)]
+#[serde(rename_all = "camelCase")]
+pub struct BddResponse {
+    pub expression: String,
+    /// The diagram converted back into an expression, if requested.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub after: Option<String>,
+    /// The atomic values in the order they are tested, from the root and down.
+    pub variables: Vec<String>,
+    /// The node of the expression, where `0` is always false and `1` is always true.
+    pub root: NodeId,
+    /// Every node reachable from the root, except the terminal nodes `0` and `1`.
+    pub nodes: Vec<BddNode>,
+    /// The number of assignments of all the variables that make the root true, if it fits.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub model_count: Option<u128>,
+}
+
+/// A LaTeX document fragment, returned as text instead of JSON.
+pub struct LatexResponse(pub String);
+
+impl IntoResponse for LatexResponse {
+    fn into_response(self) -> Response {
+        ([(header::CONTENT_TYPE, "application/x-latex; charset=utf-8")], self.0).into_response()
+    }
+}
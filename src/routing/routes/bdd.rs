@@ -0,0 +1,66 @@
+use axum::extract::{Path, Query};
+use axum::http::StatusCode;
+use axum::response::{IntoResponse, Response};
+use lib::{router, routes};
+
+use crate::expressions::bdd::{Bdd, MAX_BDD_NODES, MAX_EXPANDED_NODES};
+use crate::expressions::expression::Expression;
+use crate::expressions::sat::MAX_COUNTED_ATOMICS;
+use crate::routing::error::{Error, ErrorKind};
+use crate::routing::options::BddOptions;
+use crate::routing::response::BddResponse;
+
+router!("/bdd", routes!(
+    get "/:exp" => bdd
+));
+
+async fn bdd(Path(path): Path<String>, Query(query): Query<BddOptions>) -> Response {
+    match Expression::try_from(path.as_str()) {
+        Ok(expression) => {
+            let Some((mut bdd, mut root)) = Bdd::from_expression(&expression, query.order) else {
+                return too_many_nodes();
+            };
+            for variable in &query.exists {
+                let Some(quantified) = bdd.exists(root, variable) else {
+                    return too_many_nodes();
+                };
+                root = quantified;
+            }
+            for variable in &query.for_all {
+                let Some(quantified) = bdd.for_all(root, variable) else {
+                    return too_many_nodes();
+                };
+                root = quantified;
+            }
+            let after = if query.to_expression {
+                match bdd.to_expression(root) {
+                    Some(after) => Some(after.to_string()),
+                    None => return limit_exceeded(format!(
+                        "toExpression cannot expand more than {MAX_EXPANDED_NODES} nodes of the diagram"
+                    )),
+                }
+            } else {
+                None
+            };
+            BddResponse {
+                expression: expression.to_string(),
+                after,
+                variables: bdd.variables().to_vec(),
+                root,
+                nodes: bdd.reachable_nodes(root),
+                model_count: (bdd.variables().len() <= MAX_COUNTED_ATOMICS).then(|| bdd.count_models(root)),
+            }.into_response()
+        }
+        Err(error) => {
+            (StatusCode::BAD_REQUEST, Error::from(error)).into_response()
+        }
+    }
+}
+
+fn too_many_nodes() -> Response {
+    limit_exceeded(format!("The binary decision diagram cannot have more than {MAX_BDD_NODES} nodes"))
+}
+
+fn limit_exceeded(message: String) -> Response {
+    (StatusCode::BAD_REQUEST, Error::new(message, ErrorKind::LimitExceeded)).into_response()
+}
@@ -0,0 +1,26 @@
+use axum::extract::Path;
+use axum::http::StatusCode;
+use axum::response::{IntoResponse, Response};
+use lib::{router, routes};
+
+use crate::expressions::expression::Expression;
+use crate::routing::error::Error;
+use crate::routing::response::ClassifyResponse;
+
+router!("/classify", routes!(
+    get "/:exp" => classify
+));
+
+async fn classify(Path(path): Path<String>) -> Response {
+    match Expression::try_from(path.as_str()) {
+        Ok(expression) => {
+            ClassifyResponse {
+                expression: expression.to_string(),
+                classification: expression.classify(),
+            }.into_response()
+        }
+        Err(error) => {
+            (StatusCode::BAD_REQUEST, Error::from(error)).into_response()
+        }
+    }
+}
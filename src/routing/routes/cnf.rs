@@ -0,0 +1,42 @@
+use axum::extract::{Path, Query};
+use axum::http::StatusCode;
+use axum::response::{IntoResponse, Response};
+use lib::{router, routes};
+
+use crate::expressions::expression::Expression;
+use crate::expressions::normal_form::MAX_NORMAL_FORM_SIZE;
+use crate::routing::error::{Error, ErrorKind};
+use crate::routing::options::NotationOptions;
+use crate::routing::response::CnfResponse;
+
+router!("/cnf", routes!(
+    get "/:exp" => cnf
+));
+
+async fn cnf(Path(path): Path<String>, Query(query): Query<NotationOptions>) -> Response {
+    match Expression::try_from(path.as_str()) {
+        Ok(expression) if expression.cnf_size_bound() > MAX_NORMAL_FORM_SIZE => {
+            let message = format!("The conjunctive normal form of the expression may have more than {MAX_NORMAL_FORM_SIZE} literals");
+            (StatusCode::BAD_REQUEST, Error::new(message, ErrorKind::LimitExceeded)).into_response()
+        }
+        Ok(expression) => {
+            let (cnf, operations) = expression.to_cnf();
+            match cnf.clauses() {
+                Some(clauses) => CnfResponse {
+                    before: expression.to_string_in(query.notation),
+                    after: cnf.to_string_in(query.notation),
+                    operations: operations.into_iter().map(|operation| operation.with_notation(query.notation)).collect(),
+                    clauses,
+                    expression: cnf,
+                }.into_response(),
+                None => {
+                    let message = format!("Failed to convert {expression} to conjunctive normal form");
+                    (StatusCode::INTERNAL_SERVER_ERROR, Error::new(message, ErrorKind::Unexpected)).into_response()
+                }
+            }
+        }
+        Err(error) => {
+            (StatusCode::BAD_REQUEST, Error::from(error)).into_response()
+        }
+    }
+}
@@ -0,0 +1,42 @@
+use axum::extract::{Path, Query};
+use axum::http::StatusCode;
+use axum::response::{IntoResponse, Response};
+use lib::{router, routes};
+
+use crate::expressions::expression::Expression;
+use crate::expressions::normal_form::MAX_NORMAL_FORM_SIZE;
+use crate::routing::error::{Error, ErrorKind};
+use crate::routing::options::NotationOptions;
+use crate::routing::response::DnfResponse;
+
+router!("/dnf", routes!(
+    get "/:exp" => dnf
+));
+
+async fn dnf(Path(path): Path<String>, Query(query): Query<NotationOptions>) -> Response {
+    match Expression::try_from(path.as_str()) {
+        Ok(expression) if expression.dnf_size_bound() > MAX_NORMAL_FORM_SIZE => {
+            let message = format!("The disjunctive normal form of the expression may have more than {MAX_NORMAL_FORM_SIZE} literals");
+            (StatusCode::BAD_REQUEST, Error::new(message, ErrorKind::LimitExceeded)).into_response()
+        }
+        Ok(expression) => {
+            let (dnf, operations) = expression.to_dnf();
+            match dnf.terms() {
+                Some(terms) => DnfResponse {
+                    before: expression.to_string_in(query.notation),
+                    after: dnf.to_string_in(query.notation),
+                    operations: operations.into_iter().map(|operation| operation.with_notation(query.notation)).collect(),
+                    terms,
+                    expression: dnf,
+                }.into_response(),
+                None => {
+                    let message = format!("Failed to convert {expression} to disjunctive normal form");
+                    (StatusCode::INTERNAL_SERVER_ERROR, Error::new(message, ErrorKind::Unexpected)).into_response()
+                }
+            }
+        }
+        Err(error) => {
+            (StatusCode::BAD_REQUEST, Error::from(error)).into_response()
+        }
+    }
+}
@@ -0,0 +1,58 @@
+use axum::http::StatusCode;
+use axum::Json;
+use axum::response::{IntoResponse, Response};
+use lib::router;
+
+use crate::expressions::expression::Expression;
+use crate::expressions::truth_table::{MAX_JOINT_ATOMICS, TruthTable};
+use crate::routing::error::{Error, ErrorKind};
+use crate::routing::request::EntailsRequest;
+use crate::routing::response::{EntailmentTable, EntailsResponse};
+
+router!(
+    post "/entails" => entails
+);
+
+async fn entails(Json(body): Json<EntailsRequest>) -> Response {
+    let mut premises = Vec::with_capacity(body.premises.len());
+    for (index, premise) in body.premises.into_iter().enumerate() {
+        match Expression::try_from(premise) {
+            Ok(premise) => premises.push(premise),
+            Err(error) => return invalid_expression(&format!("Premise {}", index + 1), Error::from(error)),
+        }
+    }
+    let conclusion = match Expression::try_from(body.conclusion) {
+        Ok(conclusion) => conclusion,
+        Err(error) => return invalid_expression("The conclusion", Error::from(error)),
+    };
+    let counter_model = Expression::entailment_counter_model(&premises, &conclusion);
+    let truth_table = if body.truth_table {
+        let mut expressions = premises.clone();
+        expressions.push(conclusion.clone());
+        let Some(truth_table) = TruthTable::joint(&expressions) else {
+            let message = format!("A truth table can only be generated for up to {MAX_JOINT_ATOMICS} atomic values");
+            return (StatusCode::BAD_REQUEST, Error::new(message, ErrorKind::LimitExceeded)).into_response();
+        };
+        // The premises and the conclusion are the last columns of the joint truth table
+        let conclusion_column = truth_table.header().len() - 1;
+        Some(EntailmentTable {
+            premise_columns: (conclusion_column - premises.len()..conclusion_column).collect(),
+            conclusion_column,
+            truth_table,
+        })
+    } else {
+        None
+    };
+    EntailsResponse {
+        premises: premises.iter().map(Expression::to_string).collect(),
+        conclusion: conclusion.to_string(),
+        valid: counter_model.is_none(),
+        counter_model,
+        truth_table,
+    }.into_response()
+}
+
+fn invalid_expression(name: &str, mut error: Error) -> Response {
+    error.message = format!("{name} is invalid: {}", error.message);
+    (StatusCode::BAD_REQUEST, error).into_response()
+}
@@ -0,0 +1,36 @@
+use axum::http::StatusCode;
+use axum::Json;
+use axum::response::{IntoResponse, Response};
+use lib::router;
+
+use crate::expressions::expression::Expression;
+use crate::routing::error::Error;
+use crate::routing::request::EquivalentRequest;
+use crate::routing::response::EquivalentResponse;
+
+router!(
+    post "/equivalent" => equivalent
+);
+
+async fn equivalent(Json(body): Json<EquivalentRequest>) -> Response {
+    let left = match Expression::try_from(body.left) {
+        Ok(left) => left,
+        Err(error) => return invalid_expression("left", Error::from(error)),
+    };
+    let right = match Expression::try_from(body.right) {
+        Ok(right) => right,
+        Err(error) => return invalid_expression("right", Error::from(error)),
+    };
+    let counterexample = left.distinguishing_assignment(&right);
+    EquivalentResponse {
+        left: left.to_string(),
+        right: right.to_string(),
+        equivalent: counterexample.is_none(),
+        counterexample,
+    }.into_response()
+}
+
+fn invalid_expression(side: &str, mut error: Error) -> Response {
+    error.message = format!("The {side} expression is invalid: {}", error.message);
+    (StatusCode::BAD_REQUEST, error).into_response()
+}
@@ -7,7 +7,7 @@ use serde::Serialize;
 
 use crate::expressions::expression::Expression;
 use crate::load_html;
-use crate::routing::error::{Error, ErrorKind};
+use crate::routing::error::Error;
 use crate::routing::response::IsValidResponse;
 
 router!(
@@ -40,7 +40,7 @@ async fn open_api() -> impl IntoResponse {
 async fn is_valid(Path(path): Path<String>) -> Response {
     match Expression::try_from(path.as_str()) {
         Ok(_) => IsValidResponse::valid().into_response(),
-        Err(error) => Error::new(error.to_string(), ErrorKind::InvalidExpression).into_response()
+        Err(error) => Error::from(error).into_response()
     }
 }
 
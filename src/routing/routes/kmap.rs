@@ -0,0 +1,40 @@
+use axum::extract::{Path, Query};
+use axum::http::StatusCode;
+use axum::response::{IntoResponse, Response};
+use lib::{router, routes};
+
+use crate::expressions::expression::Expression;
+use crate::expressions::karnaugh_map::{KarnaughMap, MAX_VARIABLES, MIN_VARIABLES};
+use crate::expressions::minimize::MAX_PETRICK_PRODUCTS;
+use crate::routing::error::{Error, ErrorKind};
+use crate::routing::options::KarnaughMapOptions;
+use crate::routing::response::KarnaughMapResponse;
+
+router!("/kmap", routes!(
+    get "/:exp" => kmap
+));
+
+async fn kmap(Path(path): Path<String>, Query(query): Query<KarnaughMapOptions>) -> Response {
+    match Expression::try_from(path.as_str()) {
+        Ok(expression) => match KarnaughMap::new(&expression, query.groups) {
+            Some(karnaugh_map) => KarnaughMapResponse { karnaugh_map }.into_response(),
+            None => {
+                let variables = expression.get_atomic_values().len();
+                let error = if variables < MIN_VARIABLES {
+                    let message = format!("A Karnaugh map needs at least {MIN_VARIABLES} atomic values, but the expression has {variables}");
+                    Error::new(message, ErrorKind::InvalidOption)
+                } else if variables > MAX_VARIABLES {
+                    let message = format!("A Karnaugh map can have at most {MAX_VARIABLES} atomic values, but the expression has {variables}");
+                    Error::new(message, ErrorKind::LimitExceeded)
+                } else {
+                    let message = format!("The groups of the Karnaugh map need more than {MAX_PETRICK_PRODUCTS} products in Petrick's method");
+                    Error::new(message, ErrorKind::LimitExceeded)
+                };
+                (StatusCode::BAD_REQUEST, error).into_response()
+            }
+        },
+        Err(error) => {
+            (StatusCode::BAD_REQUEST, Error::from(error)).into_response()
+        }
+    }
+}
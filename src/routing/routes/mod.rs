@@ -1,5 +1,27 @@
+pub(crate) mod bdd;
+
+pub(crate) mod classify;
+
+pub(crate) mod cnf;
+
+pub(crate) mod dnf;
+
+pub(crate) mod entails;
+
+pub(crate) mod equivalent;
+
+pub(crate) mod nnf;
+
+pub(crate) mod sat;
+
 pub(crate) mod index;
 
+pub(crate) mod kmap;
+
+pub(crate) mod models;
+
 pub(crate) mod simplify;
 
 pub(crate) mod table;
+
+pub(crate) mod tree;
@@ -0,0 +1,66 @@
+use axum::extract::{Path, Query};
+use axum::http::StatusCode;
+use axum::response::{IntoResponse, Response};
+use lib::{router, routes};
+
+use crate::expressions::expression::Expression;
+use crate::expressions::sat::{CountLimitExceeded, MAX_COUNT_DECISIONS, MAX_COUNTED_ATOMICS};
+use crate::routing::error::{Error, ErrorKind};
+use crate::routing::options::{MAX_MODELS_LIMIT, MAX_MODELS_OFFSET, ModelsOptions};
+use crate::routing::response::{CountModelsResponse, ModelsResponse};
+
+router!("/models", routes!(
+    get "/:exp" => models,
+    get "/count/:exp" => count
+));
+
+async fn models(Path(path): Path<String>, Query(query): Query<ModelsOptions>) -> Response {
+    if query.limit > MAX_MODELS_LIMIT {
+        return limit_exceeded(format!("limit cannot be greater than {MAX_MODELS_LIMIT}"));
+    }
+    if query.offset > MAX_MODELS_OFFSET {
+        return limit_exceeded(format!("offset cannot be greater than {MAX_MODELS_OFFSET}"));
+    }
+    match Expression::try_from(path.as_str()) {
+        Ok(expression) => {
+            // One extra model is found to tell if there are more
+            let mut models = expression.models()
+                .skip(query.offset)
+                .take(query.limit + 1)
+                .collect::<Vec<_>>();
+            let has_more = models.len() > query.limit;
+            models.truncate(query.limit);
+            ModelsResponse {
+                expression: expression.to_string(),
+                models,
+                offset: query.offset,
+                limit: query.limit,
+                has_more,
+            }.into_response()
+        }
+        Err(error) => {
+            (StatusCode::BAD_REQUEST, Error::from(error)).into_response()
+        }
+    }
+}
+
+async fn count(Path(path): Path<String>) -> Response {
+    match Expression::try_from(path.as_str()) {
+        Ok(expression) => match expression.count_models() {
+            Ok(count) => CountModelsResponse { expression: expression.to_string(), count }.into_response(),
+            Err(CountLimitExceeded::AtomicValues) => {
+                limit_exceeded(format!("Models can only be counted for up to {MAX_COUNTED_ATOMICS} atomic values"))
+            }
+            Err(CountLimitExceeded::Decisions) => {
+                limit_exceeded(format!("Counting the models needs more than {MAX_COUNT_DECISIONS} decisions"))
+            }
+        },
+        Err(error) => {
+            (StatusCode::BAD_REQUEST, Error::from(error)).into_response()
+        }
+    }
+}
+
+fn limit_exceeded(message: String) -> Response {
+    (StatusCode::BAD_REQUEST, Error::new(message, ErrorKind::LimitExceeded)).into_response()
+}
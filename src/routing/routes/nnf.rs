@@ -0,0 +1,32 @@
+use axum::extract::{Path, Query};
+use axum::http::StatusCode;
+use axum::response::{IntoResponse, Response};
+use lib::{router, routes};
+
+use crate::expressions::expression::Expression;
+use crate::routing::error::Error;
+use crate::routing::options::NotationOptions;
+use crate::routing::response::SimplifyResponse;
+
+router!("/nnf", routes!(
+    get "/:exp" => nnf
+));
+
+async fn nnf(Path(path): Path<String>, Query(query): Query<NotationOptions>) -> Response {
+    match Expression::try_from(path.as_str()) {
+        Ok(expression) => {
+            let (nnf, operations) = expression.to_nnf();
+            SimplifyResponse {
+                before: expression.to_string_in(query.notation),
+                after: nnf.to_string_in(query.notation),
+                operations: operations.into_iter().map(|operation| operation.with_notation(query.notation)).collect(),
+                expression: nnf,
+                truth_table: None,
+                classification: None,
+            }.into_response()
+        }
+        Err(error) => {
+            (StatusCode::BAD_REQUEST, Error::from(error)).into_response()
+        }
+    }
+}
@@ -0,0 +1,28 @@
+use axum::extract::Path;
+use axum::http::StatusCode;
+use axum::response::{IntoResponse, Response};
+use lib::{router, routes};
+
+use crate::expressions::expression::Expression;
+use crate::routing::error::Error;
+use crate::routing::response::SatResponse;
+
+router!("/sat", routes!(
+    get "/:exp" => sat
+));
+
+async fn sat(Path(path): Path<String>) -> Response {
+    match Expression::try_from(path.as_str()) {
+        Ok(expression) => {
+            let assignment = expression.satisfying_assignment();
+            SatResponse {
+                expression: expression.to_string(),
+                satisfiable: assignment.is_some(),
+                assignment,
+            }.into_response()
+        }
+        Err(error) => {
+            (StatusCode::BAD_REQUEST, Error::from(error)).into_response()
+        }
+    }
+}
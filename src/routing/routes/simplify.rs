@@ -4,57 +4,139 @@ use axum::response::{IntoResponse, Response};
 use lib::{router, routes};
 
 use crate::expressions::expression::Expression;
+use crate::expressions::latex::derivation;
 use crate::expressions::truth_table::TruthTable;
 use crate::routing::error::{Error, ErrorKind};
-use crate::routing::options::{SimplifyAndTableOptions, SimplifyOptions};
-use crate::routing::response::SimplifyResponse;
+use crate::expressions::simplify::{LimitExceeded, Options, Strategy};
+use crate::routing::options::{DontCareOptions, MAX_ITERATIONS_LIMIT, NotationOptions, Output, OutputOptions, SimplifyAndTableOptions, SimplifyOptions};
+use crate::routing::response::{LatexResponse, SimplifyResponse};
 
 router!("/simplify", routes!(
     get "/:exp" => simplify,
     get "/table/:exp" => simplify_and_table
 ));
 
-async fn simplify(Path(path): Path<String>, Query(query): Query<SimplifyOptions>) -> Response {
+async fn simplify(
+    Path(path): Path<String>,
+    Query(query): Query<SimplifyOptions>,
+    Query(dont_care_query): Query<DontCareOptions>,
+    Query(output_query): Query<OutputOptions>,
+    Query(notation_query): Query<NotationOptions>,
+) -> Response {
+    if query.max_iterations > MAX_ITERATIONS_LIMIT {
+        return max_iterations_exceeded();
+    }
     match Expression::try_from(path.as_str()) {
         Ok(mut expression) => {
-            let before = expression.to_string();
+            let dont_cares = match dont_care_query.into_dont_cares(&expression) {
+                Ok(dont_cares) => dont_cares,
+                Err(error) => return (StatusCode::BAD_REQUEST, error).into_response(),
+            };
+            if query.simplify && query.strategy == Strategy::Laws && !dont_cares.is_empty() {
+                return dont_cares_with_laws();
+            }
+            let before = expression.clone();
+            let classification = query.classify.then(|| expression.classify());
             let mut operations = vec![];
             if query.simplify {
-                (expression, operations) = expression.simplify(query.into());
+                match expression.simplify(Options { dont_cares, ..query.into() }) {
+                    Ok(simplified) => (expression, operations) = simplified,
+                    Err(error) => return limit_exceeded(error),
+                }
+            }
+            if output_query.output == Output::Latex {
+                return LatexResponse(derivation(&before, &expression, &operations)).into_response();
             }
             SimplifyResponse {
-                before,
-                after: expression.to_string(),
-                operations,
+                before: before.to_string_in(notation_query.notation),
+                after: expression.to_string_in(notation_query.notation),
+                operations: operations.into_iter().map(|operation| operation.with_notation(notation_query.notation)).collect(),
                 expression,
                 truth_table: None,
+                classification,
             }.into_response()
         }
         Err(error) => {
-            (StatusCode::BAD_REQUEST, Error::new(error.to_string(), ErrorKind::InvalidExpression)).into_response()
+            (StatusCode::BAD_REQUEST, Error::from(error)).into_response()
         }
     }
 }
 
-async fn simplify_and_table(Path(path): Path<String>, Query(query): Query<SimplifyAndTableOptions>) -> Response {
+async fn simplify_and_table(
+    Path(path): Path<String>,
+    Query(query): Query<SimplifyAndTableOptions>,
+    Query(dont_care_query): Query<DontCareOptions>,
+    Query(output_query): Query<OutputOptions>,
+    Query(notation_query): Query<NotationOptions>,
+) -> Response {
+    if query.simplify_options.max_iterations > MAX_ITERATIONS_LIMIT {
+        return max_iterations_exceeded();
+    }
     match Expression::try_from(path.as_str()) {
         Ok(mut expression) => {
-            let before = expression.to_string();
+            let dont_cares = match dont_care_query.into_dont_cares(&expression) {
+                Ok(dont_cares) => dont_cares,
+                Err(error) => return (StatusCode::BAD_REQUEST, error).into_response(),
+            };
+            let simplify_options = &query.simplify_options;
+            if simplify_options.simplify && simplify_options.strategy == Strategy::Laws && !dont_cares.is_empty() {
+                return dont_cares_with_laws();
+            }
+            let atomics = expression.get_atomic_values();
+            let before = expression.clone();
+            let classification = query.simplify_options.classify.then(|| expression.classify());
             let mut operations = vec![];
             if query.simplify_options.simplify {
-                (expression, operations) = expression.simplify(query.simplify_options.into());
+                let options = Options { dont_cares: dont_cares.clone(), ..query.simplify_options.into() };
+                match expression.simplify(options) {
+                    Ok(simplified) => (expression, operations) = simplified,
+                    Err(error) => return limit_exceeded(error),
+                }
+            }
+            // The don't-cares can only be marked if the simplified expression has the same atomic values
+            let truth_table = if expression.get_atomic_values() == atomics {
+                TruthTable::with_dont_cares(&expression, query.table_options, &dont_cares)
+            } else {
+                TruthTable::new(&expression, query.table_options)
+            };
+            if output_query.output == Output::Latex {
+                let latex = format!("{}\n\n{}", derivation(&before, &expression, &operations), truth_table.to_latex());
+                return LatexResponse(latex).into_response();
             }
-            let truth_table = TruthTable::new(&expression, query.table_options);
             SimplifyResponse {
-                before,
-                after: expression.to_string(),
-                operations,
+                before: before.to_string_in(notation_query.notation),
+                after: expression.to_string_in(notation_query.notation),
+                operations: operations.into_iter().map(|operation| operation.with_notation(notation_query.notation)).collect(),
                 expression,
-                truth_table: Some(truth_table),
+                truth_table: Some(truth_table.with_notation(notation_query.notation)),
+                classification,
             }.into_response()
         }
         Err(error) => {
-            (StatusCode::BAD_REQUEST, Error::new(error.to_string(), ErrorKind::InvalidExpression)).into_response()
+            (StatusCode::BAD_REQUEST, Error::from(error)).into_response()
         }
     }
 }
+
+fn max_iterations_exceeded() -> Response {
+    let message = format!("maxIterations cannot be greater than {MAX_ITERATIONS_LIMIT}");
+    (StatusCode::BAD_REQUEST, Error::new(message, ErrorKind::LimitExceeded)).into_response()
+}
+
+fn dont_cares_with_laws() -> Response {
+    let message = "dontCares and dontCare can only be used with the MINIMAL_SOP and ESPRESSO strategies";
+    (StatusCode::BAD_REQUEST, Error::new(message, ErrorKind::InvalidOption)).into_response()
+}
+
+fn limit_exceeded(error: LimitExceeded) -> Response {
+    let message = match error {
+        LimitExceeded::AtomicValues { limit } => {
+            format!("The strategy cannot simplify expressions with more than {limit} atomic values")
+        }
+        LimitExceeded::Cubes { limit } => format!("The strategy cannot simplify expressions with more than {limit} cubes in a cover"),
+        LimitExceeded::Products { limit } => {
+            format!("The strategy cannot simplify expressions that need more than {limit} products in Petrick's method")
+        }
+    };
+    (StatusCode::BAD_REQUEST, Error::new(message, ErrorKind::LimitExceeded)).into_response()
+}
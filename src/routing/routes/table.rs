@@ -5,20 +5,36 @@ use lib::{router, routes};
 
 use crate::expressions::expression::Expression;
 use crate::expressions::truth_table::TruthTable;
-use crate::routing::error::{Error, ErrorKind};
-use crate::routing::options::TruthTableOptions;
-use crate::routing::response::TruthTableResponse;
+use crate::routing::error::Error;
+use crate::routing::options::{DontCareOptions, NotationOptions, Output, OutputOptions, TruthTableOptions};
+use crate::routing::response::{LatexResponse, TruthTableResponse};
 
 router!("/table", routes!(
     get "/:exp" => table
 ));
 
 // TODO Expression as input in body
-async fn table(Path(value): Path<String>, Query(query): Query<TruthTableOptions>) -> Response {
+async fn table(
+    Path(value): Path<String>,
+    Query(query): Query<TruthTableOptions>,
+    Query(dont_care_query): Query<DontCareOptions>,
+    Query(output_query): Query<OutputOptions>,
+    Query(notation_query): Query<NotationOptions>,
+) -> Response {
     match Expression::try_from(value) {
-        Ok(expression) => {
-            TruthTableResponse { truth_table: TruthTable::new(&expression, query) }.into_response()
-        }
-        Err(e) => (StatusCode::BAD_REQUEST, Error::new(e.to_string(), ErrorKind::InvalidExpression)).into_response(),
+        Ok(expression) => match dont_care_query.into_dont_cares(&expression) {
+            Ok(dont_cares) => {
+                let truth_table = TruthTable::with_dont_cares(&expression, query, &dont_cares);
+                match output_query.output {
+                    Output::Json => {
+                        let truth_table = truth_table.with_notation(notation_query.notation);
+                        TruthTableResponse { truth_table }.into_response()
+                    }
+                    Output::Latex => LatexResponse(truth_table.to_latex()).into_response(),
+                }
+            }
+            Err(error) => (StatusCode::BAD_REQUEST, error).into_response(),
+        },
+        Err(e) => (StatusCode::BAD_REQUEST, Error::from(e)).into_response(),
     }
 }
@@ -0,0 +1,28 @@
+use axum::extract::{Path, Query};
+use axum::http::{header, StatusCode};
+use axum::response::{IntoResponse, Response};
+use lib::{router, routes};
+
+use crate::expressions::expression::Expression;
+use crate::expressions::syntax_tree::{SyntaxTree, TreeFormat};
+use crate::routing::error::Error;
+use crate::routing::options::TreeOptions;
+
+router!("/tree", routes!(
+    get "/:exp" => tree
+));
+
+async fn tree(Path(path): Path<String>, Query(query): Query<TreeOptions>) -> Response {
+    match Expression::try_from(path.as_str()) {
+        Ok(expression) => {
+            let tree = SyntaxTree::new(&expression, query.shared);
+            match query.format {
+                TreeFormat::Dot => ([(header::CONTENT_TYPE, "text/vnd.graphviz; charset=utf-8")], tree.to_dot()).into_response(),
+                TreeFormat::Svg => ([(header::CONTENT_TYPE, "image/svg+xml")], tree.to_svg()).into_response(),
+            }
+        }
+        Err(error) => {
+            (StatusCode::BAD_REQUEST, Error::from(error)).into_response()
+        }
+    }
+}
@@ -1,3 +1,6 @@
+use std::fmt::Display;
+use std::str::FromStr;
+
 use serde::{de, Deserialize, Deserializer};
 
 pub(crate) const fn ret_true() -> bool {
@@ -13,4 +16,29 @@ pub(crate) fn deserialize_bool<'de, D: Deserializer<'de>>(deserializer: D) -> Re
         "false" => Ok(false),
         _ => Err(de::Error::unknown_variant(s, &["true", "false"])),
     }
-}
\ No newline at end of file
+}
+
+pub(crate) fn deserialize_from_str<'de, D, T>(deserializer: D) -> Result<T, D::Error>
+where
+    D: Deserializer<'de>,
+    T: FromStr,
+    T::Err: Display,
+{
+    let s: &str = Deserialize::deserialize(deserializer)?;
+    s.parse().map_err(de::Error::custom)
+}
+
+/// Deserializes a comma separated list, where an empty string is an empty list.
+pub(crate) fn deserialize_list<'de, D, T>(deserializer: D) -> Result<Vec<T>, D::Error>
+where
+    D: Deserializer<'de>,
+    T: FromStr,
+    T::Err: Display,
+{
+    let s: String = Deserialize::deserialize(deserializer)?;
+    s.split(',')
+        .map(str::trim)
+        .filter(|item| !item.is_empty())
+        .map(|item| item.parse().map_err(de::Error::custom))
+        .collect()
+}